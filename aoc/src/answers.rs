@@ -0,0 +1,109 @@
+use aoc_common::Part;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+// The checked-in file of known answers
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+// The expected answer for one part of a day run against one of its input files
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub day: u8,
+    pub part: Part,
+    pub file: String,
+    pub answer: String,
+}
+
+impl Entry {
+    // Parses a "<day> <part> <file> <answer>" line
+    fn parse(line: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields[..] {
+            [day, part, file, answer] => Ok(Entry {
+                day: day.parse().map_err(|_| format!("Invalid day: {}", day))?,
+                part: part
+                    .parse::<u8>()
+                    .map_err(|_| format!("Invalid part: {}", part))?
+                    .try_into()?,
+                file: file.to_string(),
+                answer: answer.to_string(),
+            }),
+            _ => Err(format!("Expected <day> <part> <file> <answer>: {}", line)),
+        }
+    }
+}
+
+pub struct Answers {
+    pub entries: Vec<Entry>,
+}
+
+impl FromStr for Answers {
+    type Err = String;
+
+    // Reads the answers, ignoring blank lines and # comments
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        let entries = contents
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| Entry::parse(line).map_err(|e| format!("line {}: {}", i + 1, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Answers { entries })
+    }
+}
+
+impl Answers {
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn get(&self, day: u8, part: Part, file: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.day == day && entry.part == part && entry.file == file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_skips_comments_and_blank_lines() {
+        let answers = "# day part file answer\n\n5 1 test.txt 143\n"
+            .parse::<Answers>()
+            .unwrap();
+        assert_eq!(
+            answers.entries,
+            vec![Entry {
+                day: 5,
+                part: Part::One,
+                file: "test.txt".to_string(),
+                answer: "143".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_from_str_reports_bad_line() {
+        let error = "5 1 test.txt 143\n5 3 test.txt 123"
+            .parse::<Answers>()
+            .err()
+            .unwrap();
+        assert!(error.starts_with("line 2:"), "{}", error);
+    }
+
+    #[test]
+    fn test_get() {
+        let answers = "5 1 test.txt 143\n5 2 test.txt 123"
+            .parse::<Answers>()
+            .unwrap();
+        assert_eq!(answers.get(5, Part::Two, "test.txt").unwrap().answer, "123");
+        assert!(answers.get(5, Part::Two, "input.txt").is_none());
+    }
+}
@@ -0,0 +1,261 @@
+use crate::cli::Globals;
+use crate::days;
+use aoc_common::{error, input, Solution};
+use std::fs::{self, OpenOptions};
+use std::hint::black_box;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Where each benchmark run is appended so the next one can compare against it
+pub const HISTORY_FILE: &str = ".aoc-bench-history";
+
+// How much slower the median can get before it is flagged. Timings are noisy,
+// so anything smaller than this is treated as the same speed
+const REGRESSION_THRESHOLD: f64 = 1.25;
+
+pub const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+// The wall time of every run of parse, part1 and part2
+pub type Timings = [Vec<Duration>; 3];
+
+// Runs a solution's phases `runs` times, timing each one separately
+pub fn measure<S: Solution>(input: &str, runs: usize) -> error::Result<Timings> {
+    let mut timings: Timings = Default::default();
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = black_box(S::parse(black_box(input))?);
+        timings[0].push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part1(&parsed)?);
+        timings[1].push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part2(&parsed)?);
+        timings[2].push(start.elapsed());
+    }
+    Ok(timings)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_timings(timings: &[Duration]) -> Self {
+        let mut sorted = timings.to_vec();
+        sorted.sort();
+        Stats {
+            min: sorted[0],
+            median: sorted[sorted.len() / 2],
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+// One line of the history file: the stats for a phase of a day from one run
+#[derive(Debug, PartialEq, Eq)]
+struct Record {
+    timestamp: u64,
+    day: u8,
+    phase: String,
+    stats: Stats,
+}
+
+impl Record {
+    fn parse(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields[..] {
+            [timestamp, day, phase, min, median, max] => Some(Record {
+                timestamp: timestamp.parse().ok()?,
+                day: day.parse().ok()?,
+                phase: phase.to_string(),
+                stats: Stats {
+                    min: Duration::from_nanos(min.parse().ok()?),
+                    median: Duration::from_nanos(median.parse().ok()?),
+                    max: Duration::from_nanos(max.parse().ok()?),
+                },
+            }),
+            _ => None,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{} {} {} {} {} {}",
+            self.timestamp,
+            self.day,
+            self.phase,
+            self.stats.min.as_nanos(),
+            self.stats.median.as_nanos(),
+            self.stats.max.as_nanos()
+        )
+    }
+}
+
+// Reads the history, skipping any line that can't be understood
+fn load_history<P: AsRef<Path>>(path: P) -> Vec<Record> {
+    fs::read_to_string(path)
+        .map(|contents| contents.lines().filter_map(Record::parse).collect())
+        .unwrap_or_default()
+}
+
+fn append_history<P: AsRef<Path>>(path: P, records: &[Record]) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for record in records {
+        writeln!(file, "{}", record.to_line())?;
+    }
+    Ok(())
+}
+
+// Compares a median against the previous run's, returning the change as a
+// percentage and whether it counts as a regression
+fn compare(previous: &Stats, current: &Stats) -> (f64, bool) {
+    let previous = previous.median.as_secs_f64();
+    let current = current.median.as_secs_f64();
+    if previous == 0.0 {
+        return (0.0, false);
+    }
+    (
+        (current / previous - 1.0) * 100.0,
+        current > previous * REGRESSION_THRESHOLD,
+    )
+}
+
+// Benchmarks one day or every day against its input.txt, printing min,
+// median and max for each phase and flagging regressions against the history
+pub fn bench(day: Option<u8>, runs: usize, globals: &Globals) -> Result<(), String> {
+    if runs == 0 {
+        return Err("--runs must be at least 1".to_string());
+    }
+
+    let days = match day {
+        Some(number) => {
+            vec![days::find(number).ok_or_else(|| format!("No solution for day {}", number))?]
+        }
+        None => days::all(),
+    };
+
+    let history = load_history(HISTORY_FILE);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+
+    println!(
+        "{:<4} {:<6} {:>12} {:>12} {:>12} {:>10}",
+        "Day", "Phase", "Min", "Median", "Max", "Previous"
+    );
+
+    let mut records = Vec::new();
+    let mut regressions = 0;
+    for day in days {
+        days::prepare(day.number, globals)?;
+        let filename = days::input_path(day.number)?;
+        let input = input::load(&filename).map_err(|e| e.to_string())?;
+        let timings = (day.bench)(&input, runs).map_err(|e| e.in_file(&filename).to_string())?;
+
+        for (phase, timings) in PHASES.iter().zip(timings) {
+            let stats = Stats::from_timings(&timings);
+            let previous = history
+                .iter()
+                .rev()
+                .find(|record| record.day == day.number && record.phase == *phase);
+
+            let comparison = match previous {
+                Some(previous) => {
+                    let (change, regressed) = compare(&previous.stats, &stats);
+                    if regressed {
+                        regressions += 1;
+                    }
+                    format!(
+                        "{:>+9.1}%{}",
+                        change,
+                        if regressed { "  REGRESSION" } else { "" }
+                    )
+                }
+                None => format!("{:>10}", "-"),
+            };
+
+            println!(
+                "{:<4} {:<6} {:>12} {:>12} {:>12} {}",
+                day.number,
+                phase,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.max),
+                comparison
+            );
+
+            records.push(Record {
+                timestamp,
+                day: day.number,
+                phase: phase.to_string(),
+                stats,
+            });
+        }
+    }
+
+    append_history(HISTORY_FILE, &records).map_err(|e| format!("{}: {}", HISTORY_FILE, e))?;
+
+    if regressions > 0 {
+        println!(
+            "{} phase(s) regressed against the previous run",
+            regressions
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    #[test]
+    fn test_stats_from_timings() {
+        let stats = Stats::from_timings(&millis(&[5, 1, 9, 3, 7]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.max, Duration::from_millis(9));
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let record = Record {
+            timestamp: 1733500000,
+            day: 6,
+            phase: "part2".to_string(),
+            stats: Stats::from_timings(&millis(&[1, 2, 3])),
+        };
+        assert_eq!(Record::parse(&record.to_line()), Some(record));
+    }
+
+    #[test]
+    fn test_record_parse_rejects_garbage() {
+        assert_eq!(Record::parse("6 part2 not numbers"), None);
+    }
+
+    #[test]
+    fn test_compare_flags_regression() {
+        let previous = Stats::from_timings(&millis(&[100]));
+        let (change, regressed) = compare(&previous, &Stats::from_timings(&millis(&[200])));
+        assert_eq!(change, 100.0);
+        assert!(regressed);
+    }
+
+    #[test]
+    fn test_compare_ignores_noise() {
+        let previous = Stats::from_timings(&millis(&[100]));
+        let (_, regressed) = compare(&previous, &Stats::from_timings(&millis(&[110])));
+        assert!(!regressed);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::answers::{self, Answers};
+use crate::cli::Globals;
+use crate::days;
+use aoc_common::{input, Part};
+use std::fmt;
+use std::path::Path;
+
+enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => f.pad("PASS"),
+            Status::Fail => f.pad("FAIL"),
+            Status::Missing => f.pad("MISSING"),
+        }
+    }
+}
+
+// Runs every solver against every input with a recorded answer (and every
+// test.txt/input.txt without one), reporting PASS, FAIL or MISSING for each.
+// test.txt and input.txt should have answers for both parts, but any other
+// file, like the test2.txt written for a part two example, is only checked
+// for the parts recorded against it
+pub fn check(filename: Option<String>, globals: &Globals) -> Result<(), String> {
+    let filename = filename.unwrap_or_else(|| answers::ANSWERS_FILE.to_string());
+    let answers = Answers::load(&filename).map_err(|e| format!("{}: {}", filename, e))?;
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for (day, file) in inputs_to_check(&answers) {
+        let solution = days::find(day);
+        if solution.is_some() {
+            days::prepare(day, globals)?;
+        }
+        let path = days::file_path(day, &file);
+        let input = input::read_to_string(&path).ok();
+
+        for part in parts_to_check(&answers, day, &file) {
+            let expected = answers.get(day, part, &file).map(|entry| &entry.answer);
+
+            let (status, detail) = match (&solution, &input, expected) {
+                (None, _, _) => (Status::Missing, "no solution for this day".to_string()),
+                (_, None, _) => (Status::Missing, "input file not found".to_string()),
+                (Some(solution), Some(input), expected) => match (solution.solve)(input, &[part]) {
+                    Err(e) => (Status::Fail, e.in_file(&path).to_string()),
+                    Ok(solved) => {
+                        let actual = solved[0].0.to_string();
+                        match expected {
+                            None => (
+                                Status::Missing,
+                                format!("no expected answer, got {}", actual),
+                            ),
+                            Some(expected) if *expected == actual => (Status::Pass, actual),
+                            Some(expected) => (
+                                Status::Fail,
+                                format!("expected {}, got {}", expected, actual),
+                            ),
+                        }
+                    }
+                },
+            };
+
+            match status {
+                Status::Pass => passed += 1,
+                Status::Fail => failed += 1,
+                Status::Missing => missing += 1,
+            }
+            println!(
+                "{:<7} day {} part {} {:<10} {}",
+                status, day, part, file, detail
+            );
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+    if failed > 0 {
+        Err(format!("{} answer(s) did not match {}", failed, filename))
+    } else {
+        Ok(())
+    }
+}
+
+// Every (day, file) pair with a recorded answer, plus each registered day's
+// standard input files that are present on disk
+fn inputs_to_check(answers: &Answers) -> Vec<(u8, String)> {
+    let mut inputs: Vec<(u8, String)> = answers
+        .entries
+        .iter()
+        .map(|entry| (entry.day, entry.file.clone()))
+        .collect();
+
+    for day in days::all() {
+        for file in days::INPUT_FILES {
+            if Path::new(&days::file_path(day.number, file)).exists() {
+                inputs.push((day.number, file.to_string()));
+            }
+        }
+    }
+
+    inputs.sort();
+    inputs.dedup();
+    inputs
+}
+
+fn parts_to_check(answers: &Answers, day: u8, file: &str) -> Vec<Part> {
+    Part::ALL
+        .into_iter()
+        .filter(|&part| days::INPUT_FILES.contains(&file) || answers.get(day, part, file).is_some())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parts_to_check() {
+        let answers = "3 1 test.txt 161\n3 2 test2.txt 48\n"
+            .parse::<Answers>()
+            .unwrap();
+        assert_eq!(parts_to_check(&answers, 3, "test.txt"), Part::ALL);
+        assert_eq!(parts_to_check(&answers, 3, "input.txt"), Part::ALL);
+        assert_eq!(parts_to_check(&answers, 3, "test2.txt"), [Part::Two]);
+    }
+}
@@ -0,0 +1,480 @@
+use aoc_common::log::Level;
+use aoc_common::{Format, Part};
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+
+pub const USAGE: &[&str] = &[
+    "run --day <day> [--part <part>] [--format text|json] [<filename>]",
+    "run --all [--format text|json]",
+    "check [<answers file>]",
+    "bench [--day <day>] [--runs <runs>]",
+    "fetch --day <day> [--base-url <url>]",
+    "submit --day <day> --part <part> [--base-url <url>]",
+    "new <day>",
+    "example --day <day> [--force] <saved puzzle page>",
+    "generate --day <day> [--seed <seed>] [--size <size>]",
+    "diff [--day <day>] [--seed <seed>] [--cases <cases>] [--size <size>]",
+];
+
+// How many times bench runs each phase unless told otherwise
+const DEFAULT_RUNS: usize = 10;
+
+// How big generated inputs are unless told otherwise
+const DEFAULT_SIZE: usize = 100;
+
+// How many inputs diff tries, and how big they are. The reference
+// implementations are slow, so the inputs are small
+const DEFAULT_CASES: u64 = 100;
+const DEFAULT_DIFF_SIZE: usize = 10;
+
+// Flags that stand alone rather than taking a value
+const SWITCHES: &[&str] = &["--all", "--force"];
+
+pub enum Command {
+    Run {
+        day: u8,
+        part: Option<Part>,
+        filename: Option<String>,
+        // Falls back to .aoc.toml, then text
+        format: Option<Format>,
+    },
+    RunAll {
+        format: Option<Format>,
+    },
+    Check {
+        filename: Option<String>,
+    },
+    Bench {
+        day: Option<u8>,
+        runs: usize,
+    },
+    Fetch {
+        day: u8,
+        base_url: Option<String>,
+    },
+    Submit {
+        day: u8,
+        part: Part,
+        base_url: Option<String>,
+    },
+    New {
+        day: u8,
+    },
+    Example {
+        day: u8,
+        page: String,
+        force: bool,
+    },
+    Generate {
+        day: u8,
+        seed: u64,
+        size: usize,
+    },
+    Diff {
+        day: Option<u8>,
+        seed: u64,
+        cases: u64,
+        size: usize,
+    },
+}
+
+// Options that can go anywhere and apply to whichever command is run
+pub struct Globals {
+    pub verbosity: Option<Level>,
+    pub threads: Option<NonZeroUsize>,
+}
+
+// Takes -v, -vv and --threads <n> out of the arguments, leaving the command's
+// own
+pub fn take_globals(args: Vec<String>) -> Result<(Globals, Vec<String>), String> {
+    let mut globals = Globals {
+        verbosity: None,
+        threads: None,
+    };
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if let Some(level) = Level::from_flag(&arg) {
+            globals.verbosity = Some(level);
+        } else if arg == "--threads" {
+            let value = args.next().ok_or("Missing value for --threads")?;
+            let threads = value
+                .parse()
+                .map_err(|_| format!("Invalid value for --threads: {}", value))?;
+            globals.threads = Some(threads);
+        } else {
+            rest.push(arg);
+        }
+    }
+    Ok((globals, rest))
+}
+
+// The flags and positional arguments that follow a subcommand
+struct Options {
+    values: HashMap<String, String>,
+    switches: Vec<String>,
+    positional: Vec<String>,
+}
+
+impl Options {
+    // Anything starting with -- must be one of the command's `flags`
+    fn parse(args: &[String], flags: &[&str]) -> Result<Self, String> {
+        let mut options = Options {
+            values: HashMap::new(),
+            switches: Vec::new(),
+            positional: Vec::new(),
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg.starts_with("--") && !flags.contains(&arg.as_str()) {
+                return Err(format!("Unknown flag: {}", arg));
+            } else if SWITCHES.contains(&arg.as_str()) {
+                options.switches.push(arg.clone());
+            } else if arg.starts_with("--") {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Missing value for {}", arg))?;
+                options.values.insert(arg.clone(), value.clone());
+            } else {
+                options.positional.push(arg.clone());
+            }
+        }
+        Ok(options)
+    }
+
+    fn switch(&self, name: &str) -> bool {
+        self.switches.iter().any(|switch| switch == name)
+    }
+
+    // The value of a flag, parsed into whatever type the caller needs
+    fn value<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.values
+            .get(name)
+            .map(|value| {
+                value
+                    .parse::<T>()
+                    .map_err(|_| format!("Invalid value for {}: {}", name, value))
+            })
+            .transpose()
+    }
+}
+
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    match args.split_first() {
+        Some((command, rest)) if command == "run" => {
+            let options = Options::parse(rest, &["--day", "--part", "--format", "--all"])?;
+            let format = options.value("--format")?;
+            if options.switch("--all") {
+                if !options.values.keys().all(|flag| flag == "--format")
+                    || !options.positional.is_empty()
+                {
+                    return Err(
+                        "--all runs every day, so takes no --day, --part or filename".to_string(),
+                    );
+                }
+                return Ok(Command::RunAll { format });
+            }
+            let day = options.value("--day")?.ok_or("Missing --day or --all")?;
+            let part = options
+                .value::<u8>("--part")?
+                .map(Part::try_from)
+                .transpose()?;
+            if options.positional.len() > 1 {
+                return Err("Expected at most one filename".to_string());
+            }
+            Ok(Command::Run {
+                day,
+                part,
+                filename: options.positional.into_iter().next(),
+                format,
+            })
+        }
+        Some((command, rest)) if command == "check" => {
+            let options = Options::parse(rest, &[])?;
+            if options.positional.len() > 1 {
+                return Err("Expected at most one answers file".to_string());
+            }
+            Ok(Command::Check {
+                filename: options.positional.into_iter().next(),
+            })
+        }
+        Some((command, rest)) if command == "bench" => {
+            let options = Options::parse(rest, &["--day", "--runs"])?;
+            Ok(Command::Bench {
+                day: options.value("--day")?,
+                runs: options.value("--runs")?.unwrap_or(DEFAULT_RUNS),
+            })
+        }
+        Some((command, rest)) if command == "fetch" => {
+            let options = Options::parse(rest, &["--day", "--base-url"])?;
+            Ok(Command::Fetch {
+                day: options.value("--day")?.ok_or("Missing --day")?,
+                base_url: options.value("--base-url")?,
+            })
+        }
+        Some((command, rest)) if command == "submit" => {
+            let options = Options::parse(rest, &["--day", "--part", "--base-url"])?;
+            Ok(Command::Submit {
+                day: options.value("--day")?.ok_or("Missing --day")?,
+                part: options
+                    .value::<u8>("--part")?
+                    .ok_or("Missing --part")?
+                    .try_into()?,
+                base_url: options.value("--base-url")?,
+            })
+        }
+        Some((command, rest)) if command == "new" => {
+            let options = Options::parse(rest, &[])?;
+            match &options.positional[..] {
+                [day] => Ok(Command::New {
+                    day: day.parse().map_err(|_| format!("Invalid day: {}", day))?,
+                }),
+                _ => Err("Expected the number of the day to create".to_string()),
+            }
+        }
+        Some((command, rest)) if command == "example" => {
+            let options = Options::parse(rest, &["--day", "--force"])?;
+            match &options.positional[..] {
+                [page] => Ok(Command::Example {
+                    day: options.value("--day")?.ok_or("Missing --day")?,
+                    page: page.clone(),
+                    force: options.switch("--force"),
+                }),
+                _ => Err("Expected the saved puzzle page to read".to_string()),
+            }
+        }
+        Some((command, rest)) if command == "generate" => {
+            let options = Options::parse(rest, &["--day", "--seed", "--size"])?;
+            Ok(Command::Generate {
+                day: options.value("--day")?.ok_or("Missing --day")?,
+                seed: options.value("--seed")?.unwrap_or_default(),
+                size: options.value("--size")?.unwrap_or(DEFAULT_SIZE),
+            })
+        }
+        Some((command, rest)) if command == "diff" => {
+            let options = Options::parse(rest, &["--day", "--seed", "--cases", "--size"])?;
+            Ok(Command::Diff {
+                day: options.value("--day")?,
+                seed: options.value("--seed")?.unwrap_or_default(),
+                cases: options.value("--cases")?.unwrap_or(DEFAULT_CASES),
+                size: options.value("--size")?.unwrap_or(DEFAULT_DIFF_SIZE),
+            })
+        }
+        Some((command, _)) => Err(format!("Unknown command: {}", command)),
+        None => Err("Missing command".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_run_day_and_part() {
+        match parse(&strings(&["run", "--day", "6", "--part", "2", "input.txt"])) {
+            Ok(Command::Run {
+                day,
+                part,
+                filename,
+                format,
+            }) => {
+                assert_eq!(day, 6);
+                assert_eq!(part, Some(Part::Two));
+                assert_eq!(filename.as_deref(), Some("input.txt"));
+                assert_eq!(format, None);
+            }
+            _ => panic!("Expected Command::Run"),
+        }
+    }
+
+    #[test]
+    fn test_parse_run_all() {
+        assert!(matches!(
+            parse(&strings(&["run", "--all"])),
+            Ok(Command::RunAll { format: None })
+        ));
+    }
+
+    #[test]
+    fn test_parse_run_json() {
+        assert!(matches!(
+            parse(&strings(&["run", "--all", "--format", "json"])),
+            Ok(Command::RunAll {
+                format: Some(Format::Json)
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_run_all_with_day() {
+        assert!(parse(&strings(&["run", "--all", "--day", "6"])).is_err());
+        assert!(parse(&strings(&["run", "--day", "6", "--all"])).is_err());
+        assert!(parse(&strings(&["run", "--all", "--part", "1"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_unknown_flag() {
+        assert_eq!(
+            parse(&strings(&["run", "--dya", "6"])).err(),
+            Some("Unknown flag: --dya".to_string())
+        );
+        assert!(parse(&strings(&["check", "--force"])).is_err());
+        assert!(parse(&strings(&["bench", "--day", "6", "--part", "1"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_check() {
+        assert!(matches!(
+            parse(&strings(&["check"])),
+            Ok(Command::Check { filename: None })
+        ));
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        match parse(&strings(&["bench", "--day", "6", "--runs", "300"])) {
+            Ok(Command::Bench { day, runs }) => {
+                assert_eq!(day, Some(6));
+                assert_eq!(runs, 300);
+            }
+            _ => panic!("Expected Command::Bench"),
+        }
+    }
+
+    #[test]
+    fn test_parse_fetch() {
+        match parse(&strings(&[
+            "fetch",
+            "--day",
+            "7",
+            "--base-url",
+            "http://localhost:8080",
+        ])) {
+            Ok(Command::Fetch { day, base_url }) => {
+                assert_eq!(day, 7);
+                assert_eq!(base_url.as_deref(), Some("http://localhost:8080"));
+            }
+            _ => panic!("Expected Command::Fetch"),
+        }
+    }
+
+    #[test]
+    fn test_parse_submit() {
+        match parse(&strings(&["submit", "--day", "6", "--part", "2"])) {
+            Ok(Command::Submit {
+                day,
+                part,
+                base_url,
+            }) => {
+                assert_eq!(day, 6);
+                assert_eq!(part, Part::Two);
+                assert_eq!(base_url, None);
+            }
+            _ => panic!("Expected Command::Submit"),
+        }
+    }
+
+    #[test]
+    fn test_parse_submit_without_part() {
+        assert!(parse(&strings(&["submit", "--day", "6"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert!(matches!(
+            parse(&strings(&["new", "7"])),
+            Ok(Command::New { day: 7 })
+        ));
+        assert!(parse(&strings(&["new"])).is_err());
+        assert!(parse(&strings(&["new", "seven"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_example() {
+        match parse(&strings(&["example", "--day", "3", "day3.html", "--force"])) {
+            Ok(Command::Example { day, page, force }) => {
+                assert_eq!(day, 3);
+                assert_eq!(page, "day3.html");
+                assert!(force);
+            }
+            _ => panic!("expected an example command"),
+        }
+        assert!(matches!(
+            parse(&strings(&["example", "--day", "3", "day3.html"])),
+            Ok(Command::Example { force: false, .. })
+        ));
+        assert!(parse(&strings(&["example", "day3.html"])).is_err());
+        assert!(parse(&strings(&["example", "--day", "3"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_generate() {
+        match parse(&strings(&["generate", "--day", "5", "--seed", "42"])) {
+            Ok(Command::Generate { day, seed, size }) => {
+                assert_eq!(day, 5);
+                assert_eq!(seed, 42);
+                assert_eq!(size, DEFAULT_SIZE);
+            }
+            _ => panic!("Expected Command::Generate"),
+        }
+    }
+
+    #[test]
+    fn test_parse_diff() {
+        match parse(&strings(&["diff", "--cases", "1000"])) {
+            Ok(Command::Diff {
+                day,
+                seed,
+                cases,
+                size,
+            }) => {
+                assert_eq!(day, None);
+                assert_eq!(seed, 0);
+                assert_eq!(cases, 1000);
+                assert_eq!(size, DEFAULT_DIFF_SIZE);
+            }
+            _ => panic!("Expected Command::Diff"),
+        }
+    }
+
+    #[test]
+    fn test_take_globals() {
+        let (globals, rest) = take_globals(strings(&[
+            "aoc",
+            "run",
+            "-v",
+            "--threads",
+            "2",
+            "--day",
+            "6",
+        ]))
+        .unwrap();
+        assert_eq!(globals.verbosity, Some(Level::Debug));
+        assert_eq!(globals.threads, NonZeroUsize::new(2));
+        assert_eq!(rest, strings(&["aoc", "run", "--day", "6"]));
+
+        assert!(take_globals(strings(&["aoc", "run", "--threads", "0"])).is_err());
+        assert!(take_globals(strings(&["aoc", "run", "--threads"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_without_day() {
+        assert!(parse(&strings(&["run"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_invalid_part() {
+        assert!(parse(&strings(&["run", "--day", "6", "--part", "3"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_missing_flag_value() {
+        assert!(parse(&strings(&["run", "--day"])).is_err());
+    }
+}
@@ -0,0 +1,215 @@
+use aoc_common::Part;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+// The Advent of Code server, unless overridden for testing
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2024;
+
+// Where the session token and base URL can be set instead of on the command line
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+// The session token can also be kept in this file, in the current directory
+// or in ~/.config/aoc/
+pub const SESSION_FILE: &str = ".aoc-session";
+
+const USER_AGENT: &str = "github.com/jonnymuir/advent2024 aoc runner";
+
+// Talks to the Advent of Code server using a logged in session
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    // Builds a client from the environment: the base URL from the argument,
+    // then AOC_BASE_URL, then the real server, and the session token from
+    // AOC_SESSION or the session file
+    pub fn from_env(base_url: Option<String>) -> Result<Self, String> {
+        let base_url = base_url
+            .or_else(|| env::var(BASE_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        let session = find_session().ok_or_else(|| {
+            format!(
+                "No session token: set {} or put it in {}",
+                SESSION_ENV, SESSION_FILE
+            )
+        })?;
+        Ok(Client::new(&base_url, &session))
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day)
+    }
+
+    // Downloads a day's puzzle input
+    pub fn input(&self, day: u8) -> Result<String, String> {
+        let url = format!("{}/input", self.day_url(day));
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|e| describe_error(day, e))?;
+        response
+            .into_string()
+            .map_err(|e| format!("{}: {}", url, e))
+    }
+
+    // Posts an answer for one part of a day, returning the page the server sends back
+    pub fn submit(&self, day: u8, part: Part, answer: &str) -> Result<String, String> {
+        let url = format!("{}/answer", self.day_url(day));
+        let level = part.to_string();
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &level), ("answer", answer)])
+            .map_err(|e| describe_error(day, e))?;
+        response
+            .into_string()
+            .map_err(|e| format!("{}: {}", url, e))
+    }
+}
+
+fn describe_error(day: u8, error: ureq::Error) -> String {
+    match error {
+        ureq::Error::Status(404, _) => format!("Day {} isn't available yet", day),
+        ureq::Error::Status(400, _) | ureq::Error::Status(401, _) => {
+            "The server rejected the session token, it may have expired".to_string()
+        }
+        error => error.to_string(),
+    }
+}
+
+fn find_session() -> Option<String> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        return Some(session);
+    }
+
+    let mut candidates = vec![PathBuf::from(SESSION_FILE)];
+    if let Some(home) = env::var_os("HOME") {
+        candidates.push(PathBuf::from(home).join(".config/aoc/session"));
+    }
+    candidates
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .map(|session| session.trim().to_string())
+        .find(|session| !session.is_empty())
+}
+
+// A one-connection-at-a-time HTTP server for testing the client against
+#[cfg(test)]
+pub mod stub {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    // What the stub saw of one request
+    #[derive(Clone, Debug, Default)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub cookie: Option<String>,
+        pub body: String,
+    }
+
+    // Serves each of the (status, body) responses in turn, recording the
+    // requests, and returns the base URL to point a client at
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, Arc<Mutex<Vec<Request>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut parts = request_line.split_whitespace();
+                let mut request = Request {
+                    method: parts.next().unwrap_or_default().to_string(),
+                    path: parts.next().unwrap_or_default().to_string(),
+                    ..Default::default()
+                };
+
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let header = header.trim_end();
+                    if header.is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        match name.to_ascii_lowercase().as_str() {
+                            "cookie" => request.cookie = Some(value.trim().to_string()),
+                            "content-length" => content_length = value.trim().parse().unwrap(),
+                            _ => {}
+                        }
+                    }
+                }
+                let mut body_bytes = vec![0; content_length];
+                reader.read_exact(&mut body_bytes).unwrap();
+                request.body = String::from_utf8(body_bytes).unwrap();
+                recorded.lock().unwrap().push(request);
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, requests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_sends_session_cookie() {
+        let (base_url, requests) = stub::serve(vec![(200, "3   4\n4   3\n")]);
+        let client = Client::new(&base_url, "abc123\n");
+
+        assert_eq!(client.input(1), Ok("3   4\n4   3\n".to_string()));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2024/day/1/input");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=abc123"));
+    }
+
+    #[test]
+    fn test_input_not_available_yet() {
+        let (base_url, _) = stub::serve(vec![(404, "Not found")]);
+        let client = Client::new(&base_url, "abc123");
+
+        assert_eq!(
+            client.input(25),
+            Err("Day 25 isn't available yet".to_string())
+        );
+    }
+}
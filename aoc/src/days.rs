@@ -0,0 +1,98 @@
+use crate::bench::{self, Timings};
+use crate::cli::Globals;
+use aoc_common::config::CONFIG_FILE;
+use aoc_common::differential::{self, Outcome};
+use aoc_common::{error, log, parallel, Answer, Config, Generate, Part, Rng, Solution};
+use std::time::{Duration, Instant};
+
+// A part's answer and how long it took to solve
+pub type Solved = (Answer, Duration);
+
+// Every day's solution, registered so the runner can dispatch to them
+pub struct Day {
+    pub number: u8,
+    pub solve: fn(&str, &[Part]) -> error::Result<Vec<Solved>>,
+    pub bench: fn(&str, usize) -> error::Result<Timings>,
+    pub generate: fn(&mut Rng, usize) -> String,
+    pub compare: fn(u64, u64, usize) -> Vec<Outcome>,
+}
+
+impl Day {
+    fn new<S: Solution + Generate>() -> Self {
+        Day {
+            number: S::DAY,
+            solve: solve_timed::<S>,
+            bench: bench::measure::<S>,
+            generate: S::generate,
+            compare: differential::compare::<S>,
+        }
+    }
+}
+
+// Parses the input once and solves each part, timing just the solving
+fn solve_timed<S: Solution>(input: &str, parts: &[Part]) -> error::Result<Vec<Solved>> {
+    let input = S::parse(input)?;
+    parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = S::run(&input, part)?;
+            Ok((answer, start.elapsed()))
+        })
+        .collect()
+}
+
+pub fn all() -> Vec<Day> {
+    vec![
+        Day::new::<day1::Day1>(),
+        Day::new::<day2::Day2>(),
+        Day::new::<day3::Day3>(),
+        Day::new::<day4::Day4>(),
+        Day::new::<day5::Day5>(),
+        Day::new::<day6::Day6>(),
+    ]
+}
+
+pub fn find(number: u8) -> Option<Day> {
+    all().into_iter().find(|day| day.number == number)
+}
+
+// The files every day directory is expected to hold
+pub const INPUT_FILES: [&str; 2] = ["test.txt", "input.txt"];
+
+// Where one of a day's files lives
+pub fn file_path(number: u8, file: &str) -> String {
+    format!("day{}/{}", number, file)
+}
+
+// A day's settings from .aoc.toml, if there is one
+pub fn config(number: u8) -> Result<Config, String> {
+    Config::load(Some(number)).map_err(|e| e.to_string())
+}
+
+// Loads a day's settings for a command that solves it, and applies its
+// verbosity and threads as the day's own binary would. Parameters are read by
+// each day's binary, and the runner has no way to hand them on, so rather than
+// give different answers every command refuses a day whose section sets one
+pub fn prepare(number: u8, globals: &Globals) -> Result<Config, String> {
+    let config = config(number)?;
+    if let Some(name) = config.params.keys().min() {
+        return Err(format!(
+            "{} sets {} for day {}, which only the day{} binary understands; run that instead",
+            CONFIG_FILE, name, number, number
+        ));
+    }
+    log::init(globals.verbosity, config.verbosity);
+    match globals.threads.or(config.threads) {
+        Some(threads) => parallel::set_threads(threads),
+        None => parallel::reset_threads(),
+    }
+    Ok(config)
+}
+
+// Where a day's puzzle input lives: wherever .aoc.toml says, or dayN/input.txt
+pub fn input_path(number: u8) -> Result<String, String> {
+    Ok(config(number)?
+        .input
+        .unwrap_or_else(|| file_path(number, "input.txt")))
+}
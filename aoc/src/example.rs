@@ -0,0 +1,407 @@
+use crate::answers::{self, Answers, Entry};
+use crate::days;
+use aoc_common::Part;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// What a saved puzzle page gives us for one part: the example it works
+// through and the answer it gets for it
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub part: Part,
+    pub input: String,
+    pub answer: String,
+}
+
+// Pulls the examples and their answers out of a puzzle page. Each part is
+// described in its own <article>, the example is its first <pre><code> block
+// and the answer is the last emphasized <code> in it. Part two usually reuses
+// part one's example, so it only gets its own if its article has one
+pub fn extract(html: &str) -> Result<Vec<Example>, String> {
+    let articles = articles(html);
+    let mut examples: Vec<Example> = Vec::new();
+    for (article, part) in articles.iter().zip(Part::ALL) {
+        let input = match code_blocks(article).into_iter().next() {
+            Some(input) => input,
+            None => match examples.first() {
+                Some(first) => first.input.clone(),
+                None => return Err(format!("No <pre><code> example in part {}", part)),
+            },
+        };
+        let answer = emphasized(article)
+            .pop()
+            .ok_or_else(|| format!("No emphasized answer in part {}", part))?;
+        examples.push(Example {
+            part,
+            input,
+            answer,
+        });
+    }
+
+    if examples.is_empty() {
+        return Err("No puzzle description found".to_string());
+    }
+    Ok(examples)
+}
+
+// The contents of every <article>, or the whole page if it has none
+fn articles(html: &str) -> Vec<&str> {
+    let mut articles = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<article") {
+        let after = &rest[start..];
+        let Some(open) = after.find('>') else { break };
+        let body = &after[open + 1..];
+        let end = body.find("</article>").unwrap_or(body.len());
+        articles.push(&body[..end]);
+        rest = &body[end..];
+    }
+    if articles.is_empty() {
+        articles.push(html);
+    }
+    articles
+}
+
+// The text of everything between `open` and `close`, in order
+fn between<'a>(html: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut found = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(open) {
+        let body = &rest[start + open.len()..];
+        let Some(end) = body.find(close) else { break };
+        found.push(&body[..end]);
+        rest = &body[end + close.len()..];
+    }
+    found
+}
+
+fn code_blocks(article: &str) -> Vec<String> {
+    between(article, "<pre><code>", "</code></pre>")
+        .into_iter()
+        .map(text)
+        .collect()
+}
+
+// Every emphasized bit of code, written either way round, in page order
+fn emphasized(article: &str) -> Vec<String> {
+    let mut found: Vec<(usize, &str)> = Vec::new();
+    for (open, close) in [
+        ("<code><em>", "</em></code>"),
+        ("<em><code>", "</code></em>"),
+    ] {
+        for inner in between(article, open, close) {
+            found.push((inner.as_ptr() as usize, inner));
+        }
+    }
+    found.sort();
+    found
+        .into_iter()
+        .map(|(_, inner)| text(inner).trim().to_string())
+        .collect()
+}
+
+// The text in a piece of HTML: tags dropped and entities decoded
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(c) = rest.chars().next() {
+        if c == '<' {
+            match rest.find('>') {
+                Some(end) => rest = &rest[end + 1..],
+                None => rest = "",
+            }
+        } else if let Some((decoded, length)) = entity(rest) {
+            text.push(decoded);
+            rest = &rest[length..];
+        } else {
+            text.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    text
+}
+
+// The character an entity at the start of `html` stands for, and its length
+fn entity(html: &str) -> Option<(char, usize)> {
+    let end = html.strip_prefix('&')?.find(';')? + 2;
+    let name = &html[1..end - 1];
+    let decoded = match name {
+        "lt" => '<',
+        "gt" => '>',
+        "amp" => '&',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)?
+        }
+    };
+    Some((decoded, end))
+}
+
+// Where each example goes: test.txt, or test2.txt for a part two example
+// that isn't part one's
+fn file_name(examples: &[Example], example: &Example) -> &'static str {
+    if example.part == Part::Two && example.input != examples[0].input {
+        "test2.txt"
+    } else {
+        "test.txt"
+    }
+}
+
+// Adds an entry among the day's other answers, before its input.txt ones
+// and any later day's. Returns None if it is already there, or an error if
+// the file has a different answer for it and `force` isn't set
+fn record(contents: &str, entry: &Entry, force: bool) -> Result<Option<String>, String> {
+    let answers: Answers = contents.parse()?;
+    let line = format!(
+        "{} {} {} {}",
+        entry.day, entry.part, entry.file, entry.answer
+    );
+
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+    if let Some(existing) = answers.get(entry.day, entry.part, &entry.file) {
+        if existing.answer == entry.answer {
+            return Ok(None);
+        }
+        if !force {
+            return Err(format!(
+                "{} already has {} for day {} part {} on {}, not {} (use --force to replace it)",
+                answers::ANSWERS_FILE,
+                existing.answer,
+                entry.day,
+                entry.part,
+                entry.file,
+                entry.answer
+            ));
+        }
+        let prefix = format!("{} {} {} ", entry.day, entry.part, entry.file);
+        lines.retain(|line| !line.trim().starts_with(&prefix));
+    }
+
+    let index = lines
+        .iter()
+        .position(|line| {
+            let mut fields = line.split_whitespace();
+            match (fields.next().map(str::parse::<u8>), fields.nth(1)) {
+                (Some(Ok(day)), Some(file)) => {
+                    day > entry.day || (day == entry.day && file == "input.txt")
+                }
+                _ => false,
+            }
+        })
+        .unwrap_or(lines.len());
+    lines.insert(index, line);
+    Ok(Some(lines.join("\n") + "\n"))
+}
+
+// Writes a day's examples from a saved puzzle page and records their answers,
+// returning what changed. Anything that would change an existing example or
+// answer needs `force`
+pub fn save(root: &Path, day: u8, html: &str, force: bool) -> Result<Vec<PathBuf>, String> {
+    let examples = extract(html)?;
+
+    // Check everything before writing anything
+    let mut writes = Vec::new();
+    for example in &examples {
+        let path = root.join(days::file_path(day, file_name(&examples, example)));
+        if writes.iter().any(|(written, _)| *written == path) {
+            continue;
+        }
+        let existing = fs::read_to_string(&path).unwrap_or_default();
+        if existing == example.input {
+            continue;
+        }
+        if !existing.is_empty() && !force {
+            return Err(format!(
+                "{} already holds a different example (use --force to replace it)",
+                path.display()
+            ));
+        }
+        writes.push((path, example.input.clone()));
+    }
+
+    let answers_path = root.join(answers::ANSWERS_FILE);
+    let mut answers = fs::read_to_string(&answers_path)
+        .map_err(|e| format!("{}: {}", answers_path.display(), e))?;
+    let mut recorded = false;
+    for example in &examples {
+        let entry = Entry {
+            day,
+            part: example.part,
+            file: file_name(&examples, example).to_string(),
+            answer: example.answer.clone(),
+        };
+        if let Some(updated) = record(&answers, &entry, force)? {
+            answers = updated;
+            recorded = true;
+        }
+    }
+    if recorded {
+        writes.push((answers_path, answers));
+    }
+
+    let mut written = Vec::new();
+    for (path, contents) in writes {
+        fs::write(&path, contents).map_err(|e| format!("{}: {}", path.display(), e))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+// Saves the examples from a puzzle page into the day in the current directory
+pub fn example(day: u8, page: String, force: bool) -> Result<(), String> {
+    let html = fs::read_to_string(&page).map_err(|e| format!("{}: {}", page, e))?;
+    let written = save(Path::new("."), day, &html, force)?;
+    if written.is_empty() {
+        println!("Day {}'s examples and answers are already up to date", day);
+    }
+    for file in written {
+        println!("Wrote {}", file.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    // Cut down from a saved puzzle page, with part two using its own example
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 3: Mull It Over ---</h2>
+<p>For example, consider the following section of corrupted memory:</p>
+<pre><code>x<em>mul(2,4)</em>%&amp;mul[3,7]!@^do_not_<em>mul(5,5)</em>+mul(32,64]then(<em>mul(11,8)mul(8,5)</em>)
+</code></pre>
+<p>Adding up the result of each instruction produces <code><em>161</em></code> (<code>2*4 + 5*5 + 11*8 + 8*5</code>).</p>
+</article>
+<p>Your puzzle answer was <code>187194524</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>x<em>mul(2,4)</em>&amp;mul[3,7]!^<em>don't()</em>_mul(5,5)+mul(32,64](mul(11,8)un<em>do()</em>?<em>mul(8,5)</em>)
+</code></pre>
+<p>This time, the sum of the results is <em><code>48</code></em> (<code>2*4 + 8*5</code>).</p>
+</article>
+</main></body></html>"#;
+
+    const PART_ONE: &str =
+        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))\n";
+    const PART_TWO: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))\n";
+
+    #[test]
+    fn test_extract() {
+        assert_eq!(
+            extract(PAGE).unwrap(),
+            vec![
+                Example {
+                    part: Part::One,
+                    input: PART_ONE.to_string(),
+                    answer: "161".to_string(),
+                },
+                Example {
+                    part: Part::Two,
+                    input: PART_TWO.to_string(),
+                    answer: "48".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_part_one_only() {
+        let page = "<article><pre><code>3   4\n4   3\n</code></pre>\
+                    <p>a total distance of <code><em>11</em></code>!</p></article>";
+        let examples = extract(page).unwrap();
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].input, "3   4\n4   3\n");
+        assert_eq!(examples[0].answer, "11");
+    }
+
+    #[test]
+    fn test_extract_without_example() {
+        assert!(extract("<article><p>No example</p></article>").is_err());
+    }
+
+    #[test]
+    fn test_text_decodes_entities() {
+        assert_eq!(
+            text("a&lt;b&gt; &amp; &#39;c&#x27; <b>d</b>&bogus;"),
+            "a<b> & 'c' d&bogus;"
+        );
+    }
+
+    #[test]
+    fn test_record_in_day_order() {
+        let contents = "# header\n3 1 input.txt 5\n4 1 test.txt 18\n";
+        let entry = Entry {
+            day: 3,
+            part: Part::Two,
+            file: "test2.txt".to_string(),
+            answer: "48".to_string(),
+        };
+        assert_eq!(
+            record(contents, &entry, false).unwrap().as_deref(),
+            Some("# header\n3 2 test2.txt 48\n3 1 input.txt 5\n4 1 test.txt 18\n")
+        );
+    }
+
+    #[test]
+    fn test_record_conflicting_answer() {
+        let contents = "3 2 test2.txt 47\n";
+        let entry = Entry {
+            day: 3,
+            part: Part::Two,
+            file: "test2.txt".to_string(),
+            answer: "48".to_string(),
+        };
+        assert!(record(contents, &entry, false).is_err());
+        assert_eq!(
+            record(contents, &entry, true).unwrap().as_deref(),
+            Some("3 2 test2.txt 48\n")
+        );
+        assert_eq!(record("3 2 test2.txt 48\n", &entry, false), Ok(None));
+    }
+
+    #[test]
+    fn test_save() {
+        let root = env::temp_dir().join(format!("aoc-example-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("day3")).unwrap();
+        fs::write(root.join("day3/test.txt"), "").unwrap();
+        fs::write(
+            root.join("answers.txt"),
+            "1 1 test.txt 11\n9 1 test.txt 1\n",
+        )
+        .unwrap();
+
+        let written = save(&root, 3, PAGE, false).unwrap();
+        assert_eq!(written.len(), 3);
+        assert_eq!(
+            fs::read_to_string(root.join("day3/test.txt")).unwrap(),
+            PART_ONE
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("day3/test2.txt")).unwrap(),
+            PART_TWO
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("answers.txt")).unwrap(),
+            "1 1 test.txt 11\n3 1 test.txt 161\n3 2 test2.txt 48\n9 1 test.txt 1\n"
+        );
+
+        // Saving again changes nothing, but a different example needs --force
+        assert!(save(&root, 3, PAGE, false).unwrap().is_empty());
+        fs::write(root.join("day3/test.txt"), "typed by hand\n").unwrap();
+        assert!(save(&root, 3, PAGE, false)
+            .unwrap_err()
+            .contains("already holds a different example"));
+        assert_eq!(save(&root, 3, PAGE, true).unwrap().len(), 1);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
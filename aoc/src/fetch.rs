@@ -0,0 +1,99 @@
+use crate::client::Client;
+use crate::days;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    // The input was already on disk, so the server wasn't asked for it
+    Cached,
+    Downloaded,
+}
+
+// Whether an input has been downloaded. `aoc new` leaves an empty input.txt
+// as a placeholder, which doesn't count
+fn is_downloaded(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+// Downloads a day's input to `path` unless it is already there. Inputs never
+// change once published, so whatever is on disk is always good to use
+pub fn fetch_input(client: &Client, day: u8, path: &Path) -> Result<Fetched, String> {
+    if is_downloaded(path) {
+        return Ok(Fetched::Cached);
+    }
+
+    let input = client.input(day)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    fs::write(path, input).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(Fetched::Downloaded)
+}
+
+// Fetches a day's input into dayN/input.txt, or wherever .aoc.toml says
+pub fn fetch(day: u8, base_url: Option<String>) -> Result<(), String> {
+    let path = days::input_path(day)?;
+    if is_downloaded(Path::new(&path)) {
+        println!("{} is already downloaded", path);
+        return Ok(());
+    }
+
+    let client = Client::from_env(base_url)?;
+    fetch_input(&client, day, Path::new(&path))?;
+    println!("Downloaded {}", path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::stub;
+    use std::env;
+
+    #[test]
+    fn test_fetch_input_downloads_once() {
+        let dir = env::temp_dir().join(format!("aoc-fetch-test-{}", std::process::id()));
+        let path = dir.join("day1/input.txt");
+        let _ = fs::remove_dir_all(&dir);
+
+        // The stub only answers once, so a second request would fail
+        let (base_url, requests) = stub::serve(vec![(200, "3   4\n")]);
+        let client = Client::new(&base_url, "abc123");
+
+        assert_eq!(fetch_input(&client, 1, &path), Ok(Fetched::Downloaded));
+        assert_eq!(fetch_input(&client, 1, &path), Ok(Fetched::Cached));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3   4\n");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_input_replaces_placeholder() {
+        let dir = env::temp_dir().join(format!("aoc-fetch-placeholder-{}", std::process::id()));
+        let path = dir.join("input.txt");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "").unwrap();
+
+        let (base_url, _) = stub::serve(vec![(200, "3   4\n")]);
+        let client = Client::new(&base_url, "abc123");
+
+        assert_eq!(fetch_input(&client, 1, &path), Ok(Fetched::Downloaded));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3   4\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_input_error_writes_nothing() {
+        let dir = env::temp_dir().join(format!("aoc-fetch-error-{}", std::process::id()));
+        let path = dir.join("input.txt");
+
+        let (base_url, _) = stub::serve(vec![(400, "")]);
+        let client = Client::new(&base_url, "expired");
+
+        assert!(fetch_input(&client, 1, &path).is_err());
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,201 @@
+mod answers;
+mod bench;
+mod check;
+mod cli;
+mod client;
+mod days;
+mod example;
+mod fetch;
+mod scaffold;
+mod submit;
+
+use aoc_common::{input, log, output, parallel, Config, Format, Part, Rng};
+use cli::{Command, Globals};
+use std::env;
+use std::time::Duration;
+
+fn main() {
+    let program = env::args().next().unwrap_or_else(|| "aoc".to_string());
+    let parsed = cli::take_globals(env::args().collect()).and_then(|(globals, args)| {
+        // The top level of .aoc.toml applies to every command
+        let config = Config::load(None).map_err(|e| e.to_string())?;
+        log::init(globals.verbosity, config.verbosity);
+        if let Some(threads) = globals.threads.or(config.threads) {
+            parallel::set_threads(threads);
+        }
+        cli::parse(&args[1..]).map(|command| (command, globals, config))
+    });
+
+    let result = match parsed {
+        Ok((command, globals, config)) => execute(command, &globals, &config),
+        Err(message) => {
+            eprintln!("{}", message);
+            for line in cli::USAGE {
+                eprintln!("Usage: {} {} [--threads <n>] [-v|-vv]", program, line);
+            }
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(message) = result {
+        eprintln!("error: {}", message);
+        std::process::exit(1);
+    }
+}
+
+fn execute(command: Command, globals: &Globals, config: &Config) -> Result<(), String> {
+    match command {
+        Command::Run {
+            day,
+            part,
+            filename,
+            format,
+        } => run_day(day, part, filename, format, globals),
+        Command::RunAll { format } => {
+            run_all(format.or(config.format).unwrap_or_default(), globals)
+        }
+        Command::Check { filename } => check::check(filename, globals),
+        Command::Bench { day, runs } => bench::bench(day, runs, globals),
+        Command::Fetch { day, base_url } => fetch::fetch(day, base_url),
+        Command::Submit {
+            day,
+            part,
+            base_url,
+        } => submit::submit(day, part, base_url, globals),
+        Command::New { day } => scaffold::new_day(day),
+        Command::Example { day, page, force } => example::example(day, page, force),
+        Command::Generate { day, seed, size } => generate(day, seed, size),
+        Command::Diff {
+            day,
+            seed,
+            cases,
+            size,
+        } => diff(day, seed, cases, size),
+    }
+}
+
+fn read_input(filename: &str) -> Result<String, String> {
+    input::load(filename).map_err(|e| e.to_string())
+}
+
+// Runs one or both parts of a day, printing each answer. The day's section
+// of .aoc.toml applies as it does to the day's own binary
+fn run_day(
+    number: u8,
+    part: Option<Part>,
+    filename: Option<String>,
+    format: Option<Format>,
+    globals: &Globals,
+) -> Result<(), String> {
+    let day = days::find(number).ok_or_else(|| format!("No solution for day {}", number))?;
+    let config = days::prepare(number, globals)?;
+    let filename = match filename {
+        Some(filename) => filename,
+        None => days::input_path(number)?,
+    };
+    let format = format.or(config.format).unwrap_or_default();
+    let input = read_input(&filename)?;
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let results = (day.solve)(&input, &parts)
+        .map_err(|e| e.in_file(input::source_name(&filename)).to_string())?;
+    for (part, (answer, elapsed)) in parts.into_iter().zip(results) {
+        let label = format!("Day {} part {}", number, part);
+        output::print_result(format, &label, number, part, &answer, elapsed);
+    }
+    Ok(())
+}
+
+// Runs every day against its input and prints a summary table, or one
+// JSON object per part
+fn run_all(format: Format, globals: &Globals) -> Result<(), String> {
+    if format == Format::Text {
+        println!(
+            "{:<4} {:>16} {:>16} {:>12}",
+            "Day", "Part 1", "Part 2", "Time"
+        );
+    }
+
+    for day in days::all() {
+        days::prepare(day.number, globals)?;
+        let filename = days::input_path(day.number)?;
+        let input = read_input(&filename)?;
+        let results: Vec<_> = (day.solve)(&input, &Part::ALL)
+            .map_err(|e| e.in_file(input::source_name(&filename)).to_string())?
+            .into_iter()
+            .zip(Part::ALL)
+            .map(|((answer, elapsed), part)| (part, answer, elapsed))
+            .collect();
+
+        match format {
+            Format::Text => println!(
+                "{:<4} {:>16} {:>16} {:>12}",
+                day.number,
+                results[0].1,
+                results[1].1,
+                format_duration(results.iter().map(|(_, _, elapsed)| *elapsed).sum())
+            ),
+            Format::Json => {
+                for (part, answer, elapsed) in &results {
+                    println!("{}", output::to_json(day.number, *part, answer, *elapsed));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// Prints a random input for a day. The same seed and size always give the
+// same input
+fn generate(number: u8, seed: u64, size: usize) -> Result<(), String> {
+    let day = days::find(number).ok_or_else(|| format!("No solution for day {}", number))?;
+    print!("{}", (day.generate)(&mut Rng::new(seed), size));
+    Ok(())
+}
+
+// Compares every registered pair of implementations on generated inputs,
+// printing the cut down input for any that disagree
+fn diff(day: Option<u8>, seed: u64, cases: u64, size: usize) -> Result<(), String> {
+    let days = match day {
+        Some(number) => {
+            vec![days::find(number).ok_or_else(|| format!("No solution for day {}", number))?]
+        }
+        None => days::all(),
+    };
+
+    let mut disagreements = 0;
+    for day in days {
+        for outcome in (day.compare)(seed, cases, size) {
+            let status = if outcome.disagreement.is_some() {
+                "FAIL"
+            } else {
+                "PASS"
+            };
+            println!(
+                "{:<4} day {} part {} {}",
+                status, day.number, outcome.part, outcome.name
+            );
+            if let Some(disagreement) = outcome.disagreement {
+                disagreements += 1;
+                print!("{}", disagreement);
+            }
+        }
+    }
+
+    if disagreements > 0 {
+        Err(format!(
+            "{} pair(s) of implementations disagreed",
+            disagreements
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
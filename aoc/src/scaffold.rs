@@ -0,0 +1,315 @@
+use crate::days;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// A file that lists every day, one per line
+struct Registration {
+    file: &'static str,
+    // What each day's line starts with, just before the day number
+    prefix: &'static str,
+    line: fn(u8) -> String,
+}
+
+// The files that register a day with the workspace and the runner
+const REGISTRATIONS: [Registration; 3] = [
+    Registration {
+        file: "Cargo.toml",
+        prefix: "\"day",
+        line: workspace_member,
+    },
+    Registration {
+        file: "aoc/Cargo.toml",
+        prefix: "day",
+        line: runner_dependency,
+    },
+    Registration {
+        file: "aoc/src/days.rs",
+        prefix: "Day::new::<day",
+        line: runner_day,
+    },
+];
+
+fn workspace_member(day: u8) -> String {
+    format!("    \"day{}\",", day)
+}
+
+fn runner_dependency(day: u8) -> String {
+    format!("day{0} = {{ path = \"../day{0}\" }}", day)
+}
+
+fn runner_day(day: u8) -> String {
+    format!("        Day::new::<day{0}::Day{0}>(),", day)
+}
+
+fn cargo_toml(day: u8) -> String {
+    format!(
+        r#"[package]
+name = "day{day}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+aoc-common = {{ path = "../common" }}
+"#
+    )
+}
+
+fn lib_rs(day: u8) -> String {
+    format!(
+        r#"mod generate;
+
+use aoc_common::error;
+use aoc_common::{{Answer, Solution}};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    const DAY: u8 = {day};
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> error::Result<Self::Input> {{
+        Ok(input.lines().map(String::from).collect())
+    }}
+
+    fn part1(_input: &Self::Input) -> error::Result<Answer> {{
+        Ok(0.into())
+    }}
+
+    fn part2(_input: &Self::Input) -> error::Result<Answer> {{
+        Ok(0.into())
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    // The answers from the puzzle's example, which lives in test.txt
+    #[test]
+    fn test_example() {{
+        let input = include_str!("../test.txt");
+        assert_eq!(Day{day}::solve_str(input), Ok([Answer::Int(0), Answer::Int(0)]));
+    }}
+}}
+"#
+    )
+}
+
+fn generate_rs(day: u8) -> String {
+    format!(
+        r#"use crate::Day{day};
+use aoc_common::{{Generate, Rng}};
+
+impl Generate for Day{day} {{
+    // Random inputs for fuzzing and comparing solutions. Empty until the
+    // input format is known
+    fn generate(_rng: &mut Rng, _size: usize) -> String {{
+        String::new()
+    }}
+}}
+"#
+    )
+}
+
+fn main_rs(day: u8) -> String {
+    format!(
+        r#"use aoc_common::{{input, output, Args, Part}};
+use day{day}::Day{day};
+
+fn main() {{
+    let args = Args::from_env::<Day{day}>(&[], &["<filename>"]);
+    let input = input::parse_file::<Day{day}>(&args.filename).unwrap_or_else(|e| e.exit());
+
+    output::print_part::<Day{day}>(args.format, &input, Part::One, "Part one");
+    output::print_part::<Day{day}>(args.format, &input, Part::Two, "Part two");
+}}
+"#
+    )
+}
+
+// The day number at the start of a line, after the given prefix
+fn registered_day(line: &str, prefix: &str) -> Option<u8> {
+    let rest = line.trim_start().strip_prefix(prefix)?;
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+// Adds the line for a day among the other days' lines, keeping them in day
+// order. Returns None if the day is already there
+fn register(contents: &str, prefix: &str, day: u8, line: &str) -> Result<Option<String>, String> {
+    let lines: Vec<&str> = contents.lines().collect();
+    let registered: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| registered_day(line, prefix).map(|day| (i, day)))
+        .collect();
+
+    if registered.iter().any(|&(_, registered)| registered == day) {
+        return Ok(None);
+    }
+
+    // Straight after the last earlier day, or before the first later one
+    let index = match registered
+        .iter()
+        .rev()
+        .find(|&&(_, registered)| registered < day)
+    {
+        Some(&(i, _)) => i + 1,
+        None => {
+            registered
+                .first()
+                .ok_or("no other days to register it next to")?
+                .0
+        }
+    };
+
+    let mut lines: Vec<&str> = lines;
+    lines.insert(index, line);
+    Ok(Some(lines.join("\n") + "\n"))
+}
+
+// Creates dayN with a skeleton solution and registers it with the workspace
+// and the runner, returning every file it wrote
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("There is no day {}, only days 1 to 25", day));
+    }
+
+    let dir = root.join(format!("day{}", day));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    // Work out every registration before writing anything, so a file we
+    // can't understand leaves the tree as it was
+    let mut updates = Vec::new();
+    for registration in REGISTRATIONS {
+        let path = root.join(registration.file);
+        let contents =
+            fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        if let Some(contents) = register(
+            &contents,
+            registration.prefix,
+            day,
+            &(registration.line)(day),
+        )
+        .map_err(|e| format!("{}: {}", path.display(), e))?
+        {
+            updates.push((path, contents));
+        }
+    }
+
+    let files = [
+        ("Cargo.toml", cargo_toml(day)),
+        ("src/lib.rs", lib_rs(day)),
+        ("src/generate.rs", generate_rs(day)),
+        ("src/main.rs", main_rs(day)),
+        ("test.txt", String::new()),
+        ("input.txt", String::new()),
+    ];
+
+    fs::create_dir_all(dir.join("src")).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let mut written = Vec::new();
+    for (file, contents) in files
+        .into_iter()
+        .map(|(file, contents)| (dir.join(file), contents))
+        .chain(updates)
+    {
+        fs::write(&file, contents).map_err(|e| format!("{}: {}", file.display(), e))?;
+        written.push(file);
+    }
+    Ok(written)
+}
+
+// Scaffolds a new day in the current directory
+pub fn new_day(day: u8) -> Result<(), String> {
+    for file in scaffold(Path::new("."), day)? {
+        println!("Wrote {}", file.display());
+    }
+    println!(
+        "Save the puzzle page and run `aoc example --day {0} <page>` to fill in {1}, put its answers into the test in src/lib.rs, then run `aoc fetch --day {0}`",
+        day,
+        days::file_path(day, "test.txt"),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const WORKSPACE: &str =
+        "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day9\",\n]\n";
+    const RUNNER_DEPENDENCIES: &str =
+        "[dependencies]\naoc-common = { path = \"../common\" }\nday1 = { path = \"../day1\" }\nureq = \"2.12.1\"\n";
+    const RUNNER_DAYS: &str =
+        "pub fn all() -> Vec<Day> {\n    vec![\n        Day::new::<day1::Day1>(),\n    ]\n}\n";
+
+    #[test]
+    fn test_register_in_day_order() {
+        let registered = register(WORKSPACE, "\"day", 7, &workspace_member(7)).unwrap();
+        assert_eq!(
+            registered.as_deref(),
+            Some("[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day7\",\n    \"day9\",\n]\n")
+        );
+    }
+
+    #[test]
+    fn test_register_twice() {
+        let registered = register(WORKSPACE, "\"day", 1, &workspace_member(1)).unwrap();
+        assert_eq!(registered, None);
+    }
+
+    #[test]
+    fn test_register_before_later_days() {
+        let contents = "members = [\n    \"day10\",\n]\n";
+        let registered = register(contents, "\"day", 2, &workspace_member(2)).unwrap();
+        assert_eq!(
+            registered.as_deref(),
+            Some("members = [\n    \"day2\",\n    \"day10\",\n]\n")
+        );
+    }
+
+    #[test]
+    fn test_register_without_other_days() {
+        assert!(register("members = []\n", "\"day", 2, &workspace_member(2)).is_err());
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), RUNNER_DEPENDENCIES).unwrap();
+        fs::write(root.join("aoc/src/days.rs"), RUNNER_DAYS).unwrap();
+
+        let written = scaffold(&root, 7).unwrap();
+        assert_eq!(written.len(), 9);
+
+        let lib = fs::read_to_string(root.join("day7/src/lib.rs")).unwrap();
+        assert!(lib.contains("impl Solution for Day7 {"));
+        assert!(lib.contains("include_str!(\"../test.txt\")"));
+        assert_eq!(fs::read_to_string(root.join("day7/input.txt")).unwrap(), "");
+        assert!(fs::read_to_string(root.join("aoc/Cargo.toml"))
+            .unwrap()
+            .contains("day1 = { path = \"../day1\" }\nday7 = { path = \"../day7\" }\n"));
+        assert!(fs::read_to_string(root.join("aoc/src/days.rs"))
+            .unwrap()
+            .contains("Day::new::<day7::Day7>(),"));
+
+        assert!(scaffold(&root, 7)
+            .unwrap_err()
+            .ends_with("day7 already exists"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_scaffold_rejects_days_outside_advent() {
+        assert!(scaffold(Path::new("."), 26).is_err());
+        assert!(scaffold(Path::new("."), 0).is_err());
+    }
+}
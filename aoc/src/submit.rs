@@ -0,0 +1,418 @@
+use crate::cli::Globals;
+use crate::client::Client;
+use crate::days;
+use aoc_common::{input, Part};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+// Every answer sent to the server, so the same guess is never sent twice
+pub const SUBMISSIONS_FILE: &str = ".aoc-submissions";
+
+// What the server made of a submitted answer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    WrongAnswer,
+    RateLimited { wait: Duration },
+    // Anything else, e.g. submitting a part that's already been solved
+    Unrecognized(String),
+}
+
+impl Verdict {
+    // The name used in the submissions file, for the verdicts worth remembering
+    fn name(&self) -> Option<&'static str> {
+        match self {
+            Verdict::Correct => Some("correct"),
+            Verdict::TooHigh => Some("too-high"),
+            Verdict::TooLow => Some("too-low"),
+            Verdict::WrongAnswer => Some("wrong"),
+            _ => None,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "correct" => Some(Verdict::Correct),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::WrongAnswer),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::TooHigh => write!(f, "Wrong, the answer is too high"),
+            Verdict::TooLow => write!(f, "Wrong, the answer is too low"),
+            Verdict::WrongAnswer => write!(f, "Wrong answer"),
+            Verdict::RateLimited { wait } => {
+                write!(f, "Answered too recently, wait {}s", wait.as_secs())
+            }
+            Verdict::Unrecognized(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+// Works out the verdict from the HTML page the server sends back
+pub fn parse_response(html: &str) -> Verdict {
+    let message = article_text(html);
+
+    if message.contains("That's the right answer") {
+        Verdict::Correct
+    } else if message.contains("You gave an answer too recently") {
+        Verdict::RateLimited {
+            wait: parse_wait(&message).unwrap_or_default(),
+        }
+    } else if message.contains("That's not the right answer") {
+        if message.contains("too high") {
+            Verdict::TooHigh
+        } else if message.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::WrongAnswer
+        }
+    } else {
+        Verdict::Unrecognized(message)
+    }
+}
+
+// The text of the page's <article>, where the server puts its reply, with the
+// tags stripped out
+fn article_text(html: &str) -> String {
+    let article = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Reads "You have 1m 23s left to wait" as a duration
+fn parse_wait(message: &str) -> Option<Duration> {
+    let end = message.find(" left to wait")?;
+    let start = message[..end].rfind("have ")? + "have ".len();
+
+    message[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+// One line of the submissions file: "day part verdict answer", with the
+// answer last so that it may have spaces in it
+#[derive(Debug, PartialEq, Eq)]
+struct Submission {
+    day: u8,
+    part: Part,
+    answer: String,
+    verdict: Verdict,
+}
+
+impl Submission {
+    // The answer comes last, so it may contain spaces
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(4, ' ');
+        let (day, part) = (fields.next()?, fields.next()?);
+        let verdict = Verdict::from_name(fields.next()?)?;
+        Some(Submission {
+            day: day.parse().ok()?,
+            part: part.parse::<u8>().ok()?.try_into().ok()?,
+            answer: fields.next()?.to_string(),
+            verdict,
+        })
+    }
+}
+
+pub struct Submissions {
+    submissions: Vec<Submission>,
+}
+
+impl Submissions {
+    pub fn load<P: AsRef<Path>>(path: P) -> Self {
+        let submissions = fs::read_to_string(path)
+            .map(|contents| contents.lines().filter_map(Submission::parse).collect())
+            .unwrap_or_default();
+        Submissions { submissions }
+    }
+
+    // Why this answer shouldn't be sent, if anything already recorded rules it out
+    pub fn reason_not_to_submit(&self, day: u8, part: Part, answer: &str) -> Option<String> {
+        let previous = self
+            .submissions
+            .iter()
+            .filter(|submission| submission.day == day && submission.part == part);
+
+        for submission in previous {
+            let number = answer.parse::<i128>().ok();
+            let bound = submission.answer.parse::<i128>().ok();
+
+            let reason =
+                match (&submission.verdict, number, bound) {
+                    (Verdict::Correct, _, _) => {
+                        Some(format!("Already solved with {}", submission.answer))
+                    }
+                    (verdict, _, _) if submission.answer == answer => {
+                        Some(format!("{} was already submitted: {}", answer, verdict))
+                    }
+                    (Verdict::TooHigh, Some(number), Some(bound)) if number >= bound => Some(
+                        format!("{} was already too high, so {} must be too", bound, number),
+                    ),
+                    (Verdict::TooLow, Some(number), Some(bound)) if number <= bound => Some(
+                        format!("{} was already too low, so {} must be too", bound, number),
+                    ),
+                    _ => None,
+                };
+            if reason.is_some() {
+                return reason;
+            }
+        }
+        None
+    }
+
+    // Remembers the verdict, if it's one worth remembering
+    pub fn record<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        day: u8,
+        part: Part,
+        answer: &str,
+        verdict: &Verdict,
+    ) -> io::Result<()> {
+        if let Some(name) = verdict.name() {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{} {} {} {}", day, part, name, answer)?;
+            self.submissions.push(Submission {
+                day,
+                part,
+                answer: answer.to_string(),
+                verdict: verdict.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+// Solves a part against dayN/input.txt and submits the answer, unless it has
+// been submitted before or an earlier verdict already rules it out
+pub fn submit(
+    day: u8,
+    part: Part,
+    base_url: Option<String>,
+    globals: &Globals,
+) -> Result<(), String> {
+    let solution = days::find(day).ok_or_else(|| format!("No solution for day {}", day))?;
+    days::prepare(day, globals)?;
+    let filename = days::input_path(day)?;
+    let input = input::load(&filename).map_err(|e| e.to_string())?;
+    let solved = (solution.solve)(&input, &[part]).map_err(|e| e.in_file(&filename).to_string())?;
+    let answer = solved[0].0.to_string();
+
+    let mut submissions = Submissions::load(SUBMISSIONS_FILE);
+    if let Some(reason) = submissions.reason_not_to_submit(day, part, &answer) {
+        return Err(format!("Not submitting: {}", reason));
+    }
+
+    let client = Client::from_env(base_url)?;
+    println!("Submitting {} for day {} part {}", answer, day, part);
+    let verdict = parse_response(&client.submit(day, part, &answer)?);
+    println!("{}", verdict);
+
+    submissions
+        .record(SUBMISSIONS_FILE, day, part, &answer, &verdict)
+        .map_err(|e| format!("{}: {}", SUBMISSIONS_FILE, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::stub;
+    use std::env;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            message
+        )
+    }
+
+    #[test]
+    fn test_parse_response_correct() {
+        let html = page("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.");
+        assert_eq!(parse_response(&html), Verdict::Correct);
+    }
+
+    #[test]
+    fn test_parse_response_too_high() {
+        let html =
+            page("That's not the right answer; your answer is too high.  If you're stuck, ...");
+        assert_eq!(parse_response(&html), Verdict::TooHigh);
+    }
+
+    #[test]
+    fn test_parse_response_too_low() {
+        let html = page("That's not the right answer; your answer is too low.");
+        assert_eq!(parse_response(&html), Verdict::TooLow);
+    }
+
+    #[test]
+    fn test_parse_response_wrong() {
+        let html = page("That's not the right answer.  If you're stuck, make sure you're using the full input data");
+        assert_eq!(parse_response(&html), Verdict::WrongAnswer);
+    }
+
+    #[test]
+    fn test_parse_response_rate_limited() {
+        let html = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait. <a href=\"/2024/day/6\">[Return to Day 6]</a>");
+        assert_eq!(
+            parse_response(&html),
+            Verdict::RateLimited {
+                wait: Duration::from_secs(83)
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_response_unrecognized() {
+        let html =
+            page("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(
+            parse_response(&html),
+            Verdict::Unrecognized(
+                "You don't seem to be solving the right level. Did you already complete it?"
+                    .to_string()
+            )
+        );
+    }
+
+    fn submissions(lines: &[&str]) -> Submissions {
+        Submissions {
+            submissions: lines
+                .iter()
+                .filter_map(|line| Submission::parse(line))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_same_wrong_answer_not_submitted_twice() {
+        let submissions = submissions(&["6 2 wrong 1234"]);
+        assert!(submissions
+            .reason_not_to_submit(6, Part::Two, "1234")
+            .is_some());
+        assert!(submissions
+            .reason_not_to_submit(6, Part::Two, "1235")
+            .is_none());
+        assert!(submissions
+            .reason_not_to_submit(6, Part::One, "1234")
+            .is_none());
+    }
+
+    #[test]
+    fn test_bounds_rule_out_answers() {
+        let submissions = submissions(&["6 2 too-high 2270", "6 2 too-low 1000"]);
+        assert!(submissions
+            .reason_not_to_submit(6, Part::Two, "3000")
+            .is_some());
+        assert!(submissions
+            .reason_not_to_submit(6, Part::Two, "999")
+            .is_some());
+        assert!(submissions
+            .reason_not_to_submit(6, Part::Two, "1500")
+            .is_none());
+    }
+
+    #[test]
+    fn test_solved_part_not_submitted_again() {
+        let submissions = submissions(&["5 1 correct 143"]);
+        assert!(submissions
+            .reason_not_to_submit(5, Part::One, "144")
+            .is_some());
+    }
+
+    #[test]
+    fn test_answer_with_spaces() {
+        let path = env::temp_dir().join(format!("aoc-submissions-text-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        Submissions::load(&path)
+            .record(&path, 5, Part::Two, "two words", &Verdict::WrongAnswer)
+            .unwrap();
+
+        let submissions = Submissions::load(&path);
+        assert!(submissions
+            .reason_not_to_submit(5, Part::Two, "two words")
+            .is_some());
+        assert!(submissions
+            .reason_not_to_submit(5, Part::Two, "two")
+            .is_none());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_record_only_remembers_verdicts() {
+        let path = env::temp_dir().join(format!("aoc-submissions-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut submissions = Submissions::load(&path);
+
+        let rate_limited = Verdict::RateLimited {
+            wait: Duration::from_secs(5),
+        };
+        submissions
+            .record(&path, 1, Part::One, "11", &rate_limited)
+            .unwrap();
+        submissions
+            .record(&path, 1, Part::One, "12", &Verdict::TooHigh)
+            .unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 1 too-high 12\n");
+        assert!(Submissions::load(&path)
+            .reason_not_to_submit(1, Part::One, "12")
+            .is_some());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_client_submit_posts_answer() {
+        let (base_url, requests) = stub::serve(vec![(
+            200,
+            "<article><p>That's the right answer!</p></article>",
+        )]);
+        let client = Client::new(&base_url, "abc123");
+
+        let verdict = parse_response(&client.submit(6, Part::Two, "6").unwrap());
+        assert_eq!(verdict, Verdict::Correct);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2024/day/6/answer");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=abc123"));
+        assert_eq!(requests[0].body, "level=2&answer=6");
+    }
+}
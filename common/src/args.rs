@@ -0,0 +1,282 @@
+use crate::config::Config;
+use crate::log::{self, Level};
+use crate::output::Format;
+use crate::parallel;
+use crate::solution::Solution;
+use std::collections::HashMap;
+use std::env;
+use std::num::NonZeroUsize;
+
+// The command line every day accepts: an input filename followed by any
+// optional positional parameters that day understands (e.g. day4's word),
+// with --format <text|json>, --threads <n> and -v or -vv allowed anywhere.
+// A parameter can also be given by name anywhere, as --<name> <value>.
+// Anything left out falls back to the day's .aoc.toml settings
+pub struct Args {
+    pub program: String,
+    pub filename: String,
+    // The day's parameters by name, from the command line or the config file
+    pub params: HashMap<String, String>,
+    pub format: Format,
+    // The log level asked for with -v or -vv, if any
+    pub verbosity: Option<Level>,
+    // How many threads to spread the work over, if not every core
+    pub threads: Option<NonZeroUsize>,
+}
+
+impl Args {
+    // Parses the process arguments for a day whose positional parameters
+    // are called `params`, printing the usage lines and exiting if there is
+    // no filename or too many parameters. Also sets the log level, from
+    // -v/-vv, the AOC_LOG variable or the config file, and the number of
+    // threads
+    pub fn from_env<S: Solution>(params: &[&str], usage: &[&str]) -> Self {
+        let config = Config::load(Some(S::DAY)).unwrap_or_else(|e| e.exit());
+        let args = Self::parse(env::args().collect(), params, &config).unwrap_or_else(|program| {
+            for line in usage {
+                eprintln!(
+                    "Usage: {} {} [--format text|json] [--threads <n>] [-v|-vv]",
+                    program, line
+                );
+            }
+            std::process::exit(1);
+        });
+        log::init(args.verbosity, config.verbosity);
+        if let Some(threads) = args.threads {
+            parallel::set_threads(threads);
+        }
+        args
+    }
+
+    // Splits the raw arguments (including the program name) into an Args,
+    // filling in whatever they leave out from the config. Returns the
+    // program name as the error so the caller can print usage
+    pub fn parse(args: Vec<String>, params: &[&str], config: &Config) -> Result<Self, String> {
+        let mut args = args.into_iter();
+        let program = args.next().unwrap_or_default();
+
+        let mut format = None;
+        let mut verbosity = None;
+        let mut threads = None;
+        let mut flags = Vec::new();
+        let mut positional = Vec::new();
+        while let Some(arg) = args.next() {
+            if let Some(level) = Level::from_flag(&arg) {
+                verbosity = Some(level);
+            } else if arg == "--threads" {
+                threads = match args.next().map(|value| value.parse()) {
+                    Some(Ok(value)) => Some(value),
+                    _ => return Err(program),
+                };
+            } else if arg == "--format" {
+                format = match args.next().map(|value| value.parse()) {
+                    Some(Ok(value)) => Some(value),
+                    _ => return Err(program),
+                };
+            } else if let Some(name) = param_flag(&arg, params) {
+                match args.next() {
+                    Some(value) => flags.push((name, value)),
+                    None => return Err(program),
+                }
+            } else {
+                positional.push(arg);
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        let filename = match positional.next().or_else(|| config.input.clone()) {
+            Some(filename) => filename,
+            None => return Err(program),
+        };
+        let given: Vec<String> = positional.collect();
+        if given.len() > params.len() {
+            return Err(program);
+        }
+
+        let mut named = config.params.clone();
+        named.extend(params.iter().map(|name| name.to_string()).zip(given));
+        named.extend(flags);
+        Ok(Args {
+            program,
+            filename,
+            params: named,
+            format: format.or(config.format).unwrap_or_default(),
+            verbosity,
+            threads: threads.or(config.threads),
+        })
+    }
+
+    // The parameter called `name`, if it was supplied
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(|param| param.as_str())
+    }
+}
+
+// The parameter a --<name> flag sets, if `arg` is one. Dashes in the name
+// stand for underscores, so --max-step sets max_step
+fn param_flag(arg: &str, params: &[&str]) -> Option<String> {
+    let name = arg.strip_prefix("--")?.replace('-', "_");
+    params.contains(&name.as_str()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_filename_only() {
+        let args = Args::parse(strings(&["day1", "input.txt"]), &[], &Config::default()).unwrap();
+        assert_eq!(args.filename, "input.txt");
+        assert!(args.params.is_empty());
+        assert_eq!(args.format, Format::Text);
+    }
+
+    #[test]
+    fn test_parse_missing_filename() {
+        assert_eq!(
+            Args::parse(strings(&["day1"]), &[], &Config::default()).err(),
+            Some("day1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_optional_param() {
+        let args = Args::parse(
+            strings(&["day4", "input.txt", "XMAS"]),
+            &["word"],
+            &Config::default(),
+        )
+        .unwrap();
+        assert_eq!(args.param("word"), Some("XMAS"));
+        assert_eq!(args.param("XMAS"), None);
+    }
+
+    #[test]
+    fn test_parse_too_many_params() {
+        assert!(Args::parse(
+            strings(&["day1", "input.txt", "extra"]),
+            &[],
+            &Config::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_named_param() {
+        let args = Args::parse(
+            strings(&["day2", "--max-step", "4", "input.txt"]),
+            &["max_step"],
+            &Config::default(),
+        )
+        .unwrap();
+        assert_eq!(args.filename, "input.txt");
+        assert_eq!(args.param("max_step"), Some("4"));
+        assert!(Args::parse(
+            strings(&["day2", "input.txt", "--max-step"]),
+            &["max_step"],
+            &Config::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_format() {
+        let args = Args::parse(
+            strings(&["day4", "--format", "json", "input.txt", "XMAS"]),
+            &["word"],
+            &Config::default(),
+        )
+        .unwrap();
+        assert_eq!(args.format, Format::Json);
+        assert_eq!(args.filename, "input.txt");
+        assert_eq!(args.param("word"), Some("XMAS"));
+    }
+
+    #[test]
+    fn test_parse_verbosity() {
+        let args = Args::parse(
+            strings(&["day6", "-vv", "input.txt"]),
+            &[],
+            &Config::default(),
+        )
+        .unwrap();
+        assert_eq!(args.verbosity, Some(Level::Trace));
+        assert_eq!(args.filename, "input.txt");
+
+        let args = Args::parse(strings(&["day6", "input.txt"]), &[], &Config::default()).unwrap();
+        assert_eq!(args.verbosity, None);
+    }
+
+    #[test]
+    fn test_parse_threads() {
+        let args = Args::parse(
+            strings(&["day6", "input.txt", "--threads", "4"]),
+            &[],
+            &Config::default(),
+        )
+        .unwrap();
+        assert_eq!(args.threads, NonZeroUsize::new(4));
+        assert!(Args::parse(
+            strings(&["day6", "input.txt", "--threads", "0"]),
+            &[],
+            &Config::default()
+        )
+        .is_err());
+        assert!(Args::parse(
+            strings(&["day6", "input.txt", "--threads"]),
+            &[],
+            &Config::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_falls_back_to_config() {
+        let config = Config {
+            input: Some("inputs/day4.txt".to_string()),
+            format: Some(Format::Json),
+            threads: NonZeroUsize::new(2),
+            params: HashMap::from([("word".to_string(), "SAMX".to_string())]),
+            ..Config::default()
+        };
+
+        let args = Args::parse(strings(&["day4"]), &["word"], &config).unwrap();
+        assert_eq!(args.filename, "inputs/day4.txt");
+        assert_eq!(args.format, Format::Json);
+        assert_eq!(args.threads, NonZeroUsize::new(2));
+        assert_eq!(args.param("word"), Some("SAMX"));
+
+        let args = Args::parse(
+            strings(&[
+                "day4",
+                "input.txt",
+                "XMAS",
+                "--format",
+                "text",
+                "--threads",
+                "1",
+            ]),
+            &["word"],
+            &config,
+        )
+        .unwrap();
+        assert_eq!(args.filename, "input.txt");
+        assert_eq!(args.format, Format::Text);
+        assert_eq!(args.threads, NonZeroUsize::new(1));
+        assert_eq!(args.param("word"), Some("XMAS"));
+    }
+
+    #[test]
+    fn test_parse_invalid_format() {
+        assert!(Args::parse(
+            strings(&["day1", "input.txt", "--format", "xml"]),
+            &[],
+            &Config::default()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,345 @@
+use crate::error::{self, Error, Result};
+use crate::log::Level;
+use crate::output::Format;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+
+// The file holding the settings, looked for in the current directory and
+// then each directory above it
+pub const CONFIG_FILE: &str = ".aoc.toml";
+
+// The environment variable that names a config file to use instead of
+// searching for one. Set it to nothing to ignore every config file
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+
+// Defaults from .aoc.toml for one day: the file's top-level keys, overlaid
+// with its [dayN] section. Command line flags override all of them. Only a
+// [dayN] section may set a day's parameters
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    // Relative paths are resolved against the directory the file is in
+    pub input: Option<String>,
+    pub format: Option<Format>,
+    pub verbosity: Option<Level>,
+    pub threads: Option<NonZeroUsize>,
+    // Every other key in the day's section, for the day to make sense of,
+    // e.g. day4's word. Lists are joined with commas, as they would be
+    // written on the command line
+    pub params: HashMap<String, String>,
+}
+
+impl Config {
+    // Reads the config file for a day, or just its top-level keys if there
+    // is no day. No file at all gives the default, empty config
+    pub fn load(day: Option<u8>) -> Result<Config> {
+        let path = match find() {
+            Some(path) => path,
+            None => return Ok(Config::default()),
+        };
+        let name = path.display().to_string();
+        let contents = fs::read_to_string(&path).map_err(|e| Error::Io {
+            file: name.clone(),
+            message: e.to_string(),
+        })?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        Config::parse(&contents, day, dir).map_err(|e| e.in_file(&name))
+    }
+
+    // Parses a config file's contents. The file format is a subset of TOML:
+    // one `key = value` per line, where the value is a string, an integer,
+    // true, false or a list of those on one line, split into [sections]
+    pub fn parse(contents: &str, day: Option<u8>, dir: &Path) -> Result<Config> {
+        let wanted = day.map(|day| format!("day{}", day));
+        let mut section: Option<String> = None;
+        let mut config = Config::default();
+        let mut ignored = Config::default();
+
+        for (i, line) in contents.lines().enumerate() {
+            let number = i + 1;
+            let text = line.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+
+            if let Some(header) = text.strip_prefix('[') {
+                let (name, rest) = header
+                    .split_once(']')
+                    .ok_or_else(|| Error::expected(number, line.len() + 1, "a closing ]"))?;
+                let name = name.trim();
+                if !is_key(name) {
+                    return Err(Error::expected(
+                        number,
+                        error::column(line, header),
+                        "a section name of letters, digits, _ and -",
+                    ));
+                }
+                end_of_line(line, rest, number)?;
+                section = Some(name.to_string());
+                continue;
+            }
+
+            let (key, value) = text
+                .split_once('=')
+                .ok_or_else(|| Error::expected(number, error::column(line, text), "key = value"))?;
+            let key = key.trim();
+            if !is_key(key) {
+                return Err(Error::expected(
+                    number,
+                    error::column(line, text),
+                    "a key of letters, digits, _ and -",
+                ));
+            }
+            // A parameter at the top level would reach every day, and no two
+            // days understand the same ones
+            if section.is_none() && !Config::is_setting(key) {
+                return Err(Error::expected(
+                    number,
+                    error::column(line, text),
+                    "input, format, verbosity or threads outside a [dayN] section",
+                ));
+            }
+            let value_text = value.trim_start();
+            let column = error::column(line, value_text);
+            let (value, rest) = parse_value(value_text).ok_or_else(|| {
+                Error::expected(
+                    number,
+                    column,
+                    "a string, number, true, false or a list of them",
+                )
+            })?;
+            end_of_line(line, rest, number)?;
+
+            // Other days' sections are checked but otherwise ignored
+            let target = if section.is_none() || section == wanted {
+                &mut config
+            } else {
+                &mut ignored
+            };
+            target
+                .set(key, value, dir)
+                .map_err(|expected| Error::expected(number, column, expected))?;
+        }
+        Ok(config)
+    }
+
+    // A day-specific parameter, if the file sets one
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(|value| value.as_str())
+    }
+
+    // Whether a key is one of the settings every day shares, rather than a
+    // parameter
+    fn is_setting(key: &str) -> bool {
+        matches!(key, "input" | "format" | "verbosity" | "threads")
+    }
+
+    fn set(&mut self, key: &str, value: String, dir: &Path) -> std::result::Result<(), &str> {
+        match key {
+            "input" => self.input = Some(dir.join(value).display().to_string()),
+            "format" => self.format = Some(value.parse().map_err(|_| "text or json")?),
+            "verbosity" => self.verbosity = Some(value.parse().map_err(|_| "off, debug or trace")?),
+            "threads" => {
+                self.threads = Some(value.parse().map_err(|_| "a number of threads above 0")?)
+            }
+            _ => {
+                self.params.insert(key.to_string(), value);
+            }
+        }
+        Ok(())
+    }
+}
+
+// The config file to use: the one AOC_CONFIG names, or the nearest one in
+// the current directory or above
+fn find() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(CONFIG_ENV) {
+        return (!path.is_empty()).then(|| PathBuf::from(path));
+    }
+    env::current_dir()
+        .ok()?
+        .ancestors()
+        .map(|dir| dir.join(CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+fn is_key(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+// Only a comment may follow a value or section header
+fn end_of_line(line: &str, rest: &str, number: usize) -> Result<()> {
+    let rest = rest.trim_start();
+    if rest.is_empty() || rest.starts_with('#') {
+        Ok(())
+    } else {
+        Err(Error::expected(
+            number,
+            error::column(line, rest),
+            "the end of the line",
+        ))
+    }
+}
+
+// Parses the value at the start of `text`, returning it and whatever follows
+fn parse_value(text: &str) -> Option<(String, &str)> {
+    if let Some(rest) = text.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => return Some((value, &rest[i + 1..])),
+                '\\' => value.push(match chars.next()?.1 {
+                    'n' => '\n',
+                    't' => '\t',
+                    c @ ('"' | '\\') => c,
+                    _ => return None,
+                }),
+                c => value.push(c),
+            }
+        }
+        None
+    } else if let Some(rest) = text.strip_prefix('\'') {
+        let end = rest.find('\'')?;
+        Some((rest[..end].to_string(), &rest[end + 1..]))
+    } else if let Some(mut rest) = text.strip_prefix('[') {
+        let mut items = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if let Some(after) = rest.strip_prefix(']') {
+                return Some((items.join(","), after));
+            }
+            let (item, after) = parse_value(rest)?;
+            items.push(item);
+            rest = after.trim_start();
+            match rest.strip_prefix(',') {
+                Some(after) => rest = after,
+                None if rest.starts_with(']') => {}
+                None => return None,
+            }
+        }
+    } else {
+        let end = text
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '+' || c == '-'))
+            .unwrap_or(text.len());
+        let (word, rest) = text.split_at(end);
+        if word == "true" || word == "false" {
+            return Some((word.to_string(), rest));
+        }
+        let number = word.replace('_', "");
+        number.parse::<i64>().ok()?;
+        Some((number.trim_start_matches('+').to_string(), rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+# Defaults for every day
+format = "json"
+verbosity = "debug"
+
+[day2]
+max_step = 4
+
+[day3]
+instructions = ["mul", "don't"]  # no do()
+input = 'inputs/day3.txt'
+format = "text"
+"#;
+
+    #[test]
+    fn test_parse_top_level() {
+        let config = Config::parse(CONFIG, None, Path::new("")).unwrap();
+        assert_eq!(config.format, Some(Format::Json));
+        assert_eq!(config.verbosity, Some(Level::Debug));
+        assert_eq!(config.input, None);
+        assert!(config.params.is_empty());
+    }
+
+    #[test]
+    fn test_parse_day_section_overrides() {
+        let config = Config::parse(CONFIG, Some(3), Path::new("/advent")).unwrap();
+        assert_eq!(config.format, Some(Format::Text));
+        assert_eq!(config.verbosity, Some(Level::Debug));
+        assert_eq!(config.input.as_deref(), Some("/advent/inputs/day3.txt"));
+        assert_eq!(config.param("instructions"), Some("mul,don't"));
+        assert_eq!(config.param("max_step"), None);
+
+        let config = Config::parse(CONFIG, Some(2), Path::new("")).unwrap();
+        assert_eq!(config.param("max_step"), Some("4"));
+    }
+
+    #[test]
+    fn test_parse_values() {
+        assert_eq!(
+            parse_value(r#""a \"b\"\\" rest"#),
+            Some((r#"a "b"\"#.to_string(), " rest"))
+        );
+        assert_eq!(parse_value("-1_000"), Some(("-1000".to_string(), "")));
+        assert_eq!(parse_value("true"), Some(("true".to_string(), "")));
+        assert_eq!(parse_value("[ 1, 'x' , ]"), Some(("1,x".to_string(), "")));
+        assert_eq!(parse_value("[]"), Some((String::new(), "")));
+        assert_eq!(parse_value("XMAS"), None);
+        assert_eq!(parse_value("\"unterminated"), None);
+        assert_eq!(parse_value("[1 2]"), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Config::parse("[day4]\nword = XMAS\n", Some(4), Path::new("")),
+            Err(Error::expected(
+                2,
+                8,
+                "a string, number, true, false or a list of them"
+            ))
+        );
+        assert_eq!(
+            Config::parse("format = \"yaml\"\n", None, Path::new("")),
+            Err(Error::expected(1, 10, "text or json"))
+        );
+        assert_eq!(
+            Config::parse("threads = 0\n", None, Path::new("")),
+            Err(Error::expected(1, 11, "a number of threads above 0"))
+        );
+        assert_eq!(
+            Config::parse("[day4]\nword = \"XMAS\" \"SAMX\"\n", None, Path::new("")),
+            Err(Error::expected(2, 15, "the end of the line"))
+        );
+        assert_eq!(
+            Config::parse("[day4\n", None, Path::new("")),
+            Err(Error::expected(1, 6, "a closing ]"))
+        );
+        assert_eq!(
+            Config::parse("word\n", None, Path::new("")),
+            Err(Error::expected(1, 1, "key = value"))
+        );
+    }
+
+    #[test]
+    fn test_parameters_only_in_day_sections() {
+        assert_eq!(
+            Config::parse("word = \"XMAS\"\n[day4]\n", Some(4), Path::new("")),
+            Err(Error::expected(
+                1,
+                1,
+                "input, format, verbosity or threads outside a [dayN] section"
+            ))
+        );
+        let config = Config::parse("[day4]\nword = \"XMAS\"\n", Some(5), Path::new("")).unwrap();
+        assert_eq!(config.param("word"), None);
+    }
+
+    #[test]
+    fn test_other_days_are_still_checked() {
+        assert!(Config::parse("[day9]\nformat = \"yaml\"\n", Some(1), Path::new("")).is_err());
+    }
+}
@@ -0,0 +1,237 @@
+use crate::error;
+use crate::random::{Generate, Rng};
+use crate::solution::{Answer, Part, Solution};
+use std::fmt;
+
+// Two ways of solving the same part: a slow one that is easy to trust and a
+// fast one that is meant to give the same answers
+pub struct Variant<I> {
+    pub part: Part,
+    // What is being compared, e.g. "Rules::correct vs Rules::correct_by_counting"
+    pub name: &'static str,
+    pub reference: fn(&I) -> error::Result<Answer>,
+    pub candidate: fn(&I) -> error::Result<Answer>,
+}
+
+// The first generated input the two implementations gave different answers
+// for, cut down to as little as still shows the difference
+#[derive(Debug, PartialEq, Eq)]
+pub struct Disagreement {
+    pub seed: u64,
+    pub input: String,
+    pub reference: error::Result<Answer>,
+    pub candidate: error::Result<Answer>,
+}
+
+// How one variant fared
+pub struct Outcome {
+    pub part: Part,
+    pub name: &'static str,
+    pub disagreement: Option<Disagreement>,
+}
+
+// Solves an input both ways, or None if it doesn't parse. Failing to work
+// out an answer only counts as agreeing if both fail the same way
+type Answers = (error::Result<Answer>, error::Result<Answer>);
+
+fn answers<S: Solution>(variant: &Variant<S::Input>, input: &str) -> Option<Answers> {
+    let input = S::parse(input).ok()?;
+    Some(((variant.reference)(&input), (variant.candidate)(&input)))
+}
+
+fn disagrees<S: Solution>(variant: &Variant<S::Input>, input: &str) -> bool {
+    matches!(answers::<S>(variant, input), Some((reference, candidate)) if reference != candidate)
+}
+
+// Removes pieces of the input for as long as it still parses and the two
+// implementations still disagree. Whole lines go first, in ever smaller
+// chunks, then characters within each remaining line
+pub fn minimize<S: Solution>(variant: &Variant<S::Input>, input: &str) -> String {
+    let still_fails = |lines: &[String]| disagrees::<S>(variant, &(lines.join("\n") + "\n"));
+
+    let mut lines: Vec<String> = input.lines().map(String::from).collect();
+    lines = shrink(lines, |lines| still_fails(lines));
+
+    for i in 0..lines.len() {
+        let chars: Vec<char> = lines[i].chars().collect();
+        let chars = shrink(chars, |chars| {
+            let mut candidate = lines.clone();
+            candidate[i] = chars.iter().collect();
+            still_fails(&candidate)
+        });
+        lines[i] = chars.into_iter().collect();
+    }
+
+    lines.join("\n") + "\n"
+}
+
+// Delta debugging: tries removing chunks of items, halving the chunk size
+// whenever no chunk of the current size can go
+fn shrink<T: Clone, F: Fn(&[T]) -> bool>(mut items: Vec<T>, still_fails: F) -> Vec<T> {
+    let mut chunk = items.len().div_ceil(2);
+    while chunk > 0 {
+        let mut start = 0;
+        let mut removed = false;
+        while start < items.len() {
+            let end = (start + chunk).min(items.len());
+            let candidate: Vec<T> = [&items[..start], &items[end..]].concat();
+            if still_fails(&candidate) {
+                items = candidate;
+                removed = true;
+            } else {
+                start += chunk;
+            }
+        }
+        if !removed {
+            chunk /= 2;
+        }
+    }
+    items
+}
+
+// Runs every variant of a day on `cases` generated inputs, seeded from
+// `seed` upwards, stopping each at its first disagreement
+pub fn compare<S: Solution + Generate>(seed: u64, cases: u64, size: usize) -> Vec<Outcome> {
+    S::variants()
+        .into_iter()
+        .map(|variant| {
+            let disagreement = (seed..seed.saturating_add(cases)).find_map(|seed| {
+                let input = S::generate(&mut Rng::new(seed), size);
+                if !disagrees::<S>(&variant, &input) {
+                    return None;
+                }
+                let input = minimize::<S>(&variant, &input);
+                let (reference, candidate) = answers::<S>(&variant, &input)?;
+                Some(Disagreement {
+                    seed,
+                    input,
+                    reference,
+                    candidate,
+                })
+            });
+            Outcome {
+                part: variant.part,
+                name: variant.name,
+                disagreement,
+            }
+        })
+        .collect()
+}
+
+// Panics with the cut down input if any of a day's variants disagree, for
+// the days' tests
+pub fn assert_agree<S: Solution + Generate>(seed: u64, cases: u64, size: usize) {
+    for outcome in compare::<S>(seed, cases, size) {
+        if let Some(disagreement) = outcome.disagreement {
+            panic!(
+                "day {} part {} ({}) disagrees on {}",
+                S::DAY,
+                outcome.part,
+                outcome.name,
+                disagreement
+            );
+        }
+    }
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "seed {}: reference gave {}, candidate gave {} for",
+            self.seed,
+            outcome(&self.reference),
+            outcome(&self.candidate)
+        )?;
+        write!(f, "{}", self.input)
+    }
+}
+
+fn outcome(answer: &error::Result<Answer>) -> String {
+    match answer {
+        Ok(answer) => answer.to_string(),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts the lines with an x in them, except that part 2's candidate
+    // miscounts any line with two
+    struct CountX;
+
+    impl Solution for CountX {
+        const DAY: u8 = 0;
+
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> error::Result<Self::Input> {
+            Ok(input.lines().map(String::from).collect())
+        }
+
+        fn part1(input: &Self::Input) -> error::Result<Answer> {
+            Ok(input
+                .iter()
+                .filter(|line| line.contains('x'))
+                .count()
+                .into())
+        }
+
+        fn part2(input: &Self::Input) -> error::Result<Answer> {
+            Self::part1(input)
+        }
+
+        fn variants() -> Vec<Variant<Self::Input>> {
+            vec![Variant {
+                part: Part::Two,
+                name: "contains vs matches",
+                reference: Self::part2,
+                candidate: |input| {
+                    Ok(input
+                        .iter()
+                        .filter(|line| line.matches('x').count() == 1)
+                        .count()
+                        .into())
+                },
+            }]
+        }
+    }
+
+    impl Generate for CountX {
+        fn generate(rng: &mut Rng, size: usize) -> String {
+            (0..size)
+                .map(|_| {
+                    let line: String = (0..8).map(|_| *rng.choose(&['a', 'b', 'x'])).collect();
+                    line + "\n"
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_compare_finds_and_minimizes_disagreement() {
+        let outcomes = compare::<CountX>(0, 10, 20);
+        assert_eq!(outcomes.len(), 1);
+        let disagreement = outcomes[0].disagreement.as_ref().unwrap();
+        assert_eq!(disagreement.seed, 0);
+        assert_eq!(disagreement.input, "xx\n");
+        assert_eq!(disagreement.reference, Ok(Answer::Int(1)));
+        assert_eq!(disagreement.candidate, Ok(Answer::Int(0)));
+    }
+
+    #[test]
+    fn test_compare_agreeing_inputs() {
+        // An empty input has no lines to miscount
+        let outcomes = compare::<CountX>(0, 10, 0);
+        assert_eq!(outcomes[0].disagreement, None);
+    }
+
+    #[test]
+    fn test_shrink_keeps_what_matters() {
+        let items: Vec<u32> = (0..100).collect();
+        let shrunk = shrink(items, |items| items.contains(&17) && items.contains(&83));
+        assert_eq!(shrunk, vec![17, 83]);
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt;
+use std::str::FromStr;
+
+// Where in the input something went wrong. Lines and columns count from 1,
+// and the file is filled in by whoever read the input
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Location {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    pub fn new(line: usize, column: usize) -> Self {
+        Location {
+            file: None,
+            line,
+            column,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}",
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column
+        )
+    }
+}
+
+// Everything that can go wrong reading and parsing a puzzle input
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    // The input file couldn't be read
+    Io {
+        file: String,
+        message: String,
+    },
+    // The input has no lines at all
+    EmptyInput {
+        file: Option<String>,
+    },
+    // A number was expected but the text there isn't one (or is too big)
+    InvalidNumber {
+        location: Location,
+        text: String,
+    },
+    // Something else the day's format requires wasn't found
+    Expected {
+        location: Location,
+        expected: String,
+    },
+    // An answer is too big for the type it's added up in
+    Overflow {
+        what: String,
+        kind: String,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn expected(line: usize, column: usize, expected: &str) -> Self {
+        Error::Expected {
+            location: Location::new(line, column),
+            expected: expected.to_string(),
+        }
+    }
+
+    pub fn overflow(what: &str, kind: &str) -> Self {
+        Error::Overflow {
+            what: what.to_string(),
+            kind: kind.to_string(),
+        }
+    }
+
+    // Records which file the input came from
+    pub fn in_file(self, file: &str) -> Self {
+        match self {
+            Error::EmptyInput { .. } => Error::EmptyInput {
+                file: Some(file.to_string()),
+            },
+            Error::InvalidNumber { location, text } => Error::InvalidNumber {
+                location: Location {
+                    file: Some(file.to_string()),
+                    ..location
+                },
+                text,
+            },
+            Error::Expected { location, expected } => Error::Expected {
+                location: Location {
+                    file: Some(file.to_string()),
+                    ..location
+                },
+                expected,
+            },
+            error => error,
+        }
+    }
+
+    // Moves the error to another line, for parsers that only see one line
+    pub fn on_line(self, line: usize) -> Self {
+        match self {
+            Error::InvalidNumber { location, text } => Error::InvalidNumber {
+                location: Location { line, ..location },
+                text,
+            },
+            Error::Expected { location, expected } => Error::Expected {
+                location: Location { line, ..location },
+                expected,
+            },
+            error => error,
+        }
+    }
+
+    // Prints the error and exits with a non-zero status
+    pub fn exit(&self) -> ! {
+        eprintln!("error: {}", self);
+        std::process::exit(1)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { file, message } => write!(f, "{}: {}", file, message),
+            Error::EmptyInput { file } => {
+                write!(
+                    f,
+                    "{}: input is empty",
+                    file.as_deref().unwrap_or("<input>")
+                )
+            }
+            Error::InvalidNumber { location, text } => {
+                write!(f, "{}: invalid number {:?}", location, text)
+            }
+            Error::Expected { location, expected } => {
+                write!(f, "{}: expected {}", location, expected)
+            }
+            Error::Overflow { what, kind } => write!(f, "{} doesn't fit in {}", what, kind),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// Parses the number `text` found at `column` of `line`
+pub fn parse_number<T: FromStr>(text: &str, line: usize, column: usize) -> Result<T> {
+    text.parse::<T>().map_err(|_| Error::InvalidNumber {
+        location: Location::new(line, column),
+        text: text.to_string(),
+    })
+}
+
+// The 1-based column at which `field` starts, where `field` is a slice of `line`
+// (as returned by split, trim and friends)
+pub fn column(line: &str, field: &str) -> usize {
+    field.as_ptr() as usize - line.as_ptr() as usize + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number::<u32>("42", 1, 1), Ok(42));
+        assert_eq!(
+            parse_number::<u32>("4x", 3, 7),
+            Err(Error::InvalidNumber {
+                location: Location::new(3, 7),
+                text: "4x".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_column_of_field() {
+        let line = "3   4";
+        let (left, right) = line.split_once("   ").unwrap();
+        assert_eq!(column(line, left), 1);
+        assert_eq!(column(line, right), 5);
+    }
+
+    #[test]
+    fn test_display_with_file() {
+        let error = parse_number::<u32>("x", 2, 5)
+            .unwrap_err()
+            .in_file("input.txt");
+        assert_eq!(error.to_string(), "input.txt:2:5: invalid number \"x\"");
+    }
+
+    #[test]
+    fn test_display_without_file() {
+        let error = Error::expected(1, 2, "|").on_line(4);
+        assert_eq!(error.to_string(), "<input>:4:2: expected |");
+    }
+
+    #[test]
+    fn test_display_overflow() {
+        let error = Error::overflow("the total", "u64").in_file("input.txt");
+        assert_eq!(error.to_string(), "the total doesn't fit in u64");
+    }
+}
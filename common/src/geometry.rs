@@ -0,0 +1,277 @@
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+// A position on a grid. Signed, so stepping off the top or left edge gives a
+// point that is simply out of bounds rather than an underflow. x increases to
+// the east and y to the south, the same way the puzzle input reads
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+// The difference between two points, e.g. one step in a direction
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    // Moves the point by a vector, or None if either coordinate would overflow
+    pub fn checked_add(self, vector: Vector) -> Option<Point> {
+        Some(Point::new(
+            self.x.checked_add(vector.x)?,
+            self.y.checked_add(vector.y)?,
+        ))
+    }
+
+    // The number of orthogonal steps between two points
+    pub fn manhattan_distance(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    // The number of steps between two points when diagonal steps are allowed
+    pub fn chebyshev_distance(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+impl Vector {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Vector { x, y }
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point::new(x as i64, y as i64)
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, vector: Vector) -> Point {
+        Point::new(self.x + vector.x, self.y + vector.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, scale: i64) -> Vector {
+        Vector::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+// The four orthogonal directions
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction4 {
+    // Clockwise from north
+    pub const ALL: [Direction4; 4] = [
+        Direction4::North,
+        Direction4::East,
+        Direction4::South,
+        Direction4::West,
+    ];
+
+    // Turns 90 degrees clockwise
+    pub fn rotate_cw(self) -> Direction4 {
+        Direction4::ALL[(self as usize + 1) % 4]
+    }
+
+    // Turns 90 degrees anticlockwise
+    pub fn rotate_ccw(self) -> Direction4 {
+        Direction4::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Direction4 {
+        Direction4::ALL[(self as usize + 2) % 4]
+    }
+
+    // One step in this direction
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction4::North => Vector::new(0, -1),
+            Direction4::East => Vector::new(1, 0),
+            Direction4::South => Vector::new(0, 1),
+            Direction4::West => Vector::new(-1, 0),
+        }
+    }
+}
+
+// The four orthogonal directions and the four diagonals between them
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    // Clockwise from north
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    // The diagonals only, clockwise from north east
+    pub const DIAGONALS: [Direction8; 4] = [
+        Direction8::NorthEast,
+        Direction8::SouthEast,
+        Direction8::SouthWest,
+        Direction8::NorthWest,
+    ];
+
+    // Turns 45 degrees clockwise
+    pub fn rotate_cw(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 1) % 8]
+    }
+
+    // Turns 45 degrees anticlockwise
+    pub fn rotate_ccw(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+
+    // One step in this direction
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction8::North => Vector::new(0, -1),
+            Direction8::NorthEast => Vector::new(1, -1),
+            Direction8::East => Vector::new(1, 0),
+            Direction8::SouthEast => Vector::new(1, 1),
+            Direction8::South => Vector::new(0, 1),
+            Direction8::SouthWest => Vector::new(-1, 1),
+            Direction8::West => Vector::new(-1, 0),
+            Direction8::NorthWest => Vector::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        match direction {
+            Direction4::North => Direction8::North,
+            Direction4::East => Direction8::East,
+            Direction4::South => Direction8::South,
+            Direction4::West => Direction8::West,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_add() {
+        assert_eq!(
+            Point::new(0, 0).checked_add(Direction4::North.vector()),
+            Some(Point::new(0, -1))
+        );
+        assert_eq!(Point::new(i64::MAX, 0).checked_add(Vector::new(1, 0)), None);
+    }
+
+    #[test]
+    fn test_point_arithmetic() {
+        let point = Point::new(2, 3);
+        assert_eq!(point + Vector::new(-1, 1) * 2, Point::new(0, 5));
+        assert_eq!(Point::new(0, 5) - point, Vector::new(-2, 2));
+        assert_eq!(-Vector::new(-2, 2), Vector::new(2, -2));
+    }
+
+    #[test]
+    fn test_distances() {
+        let (a, b) = (Point::new(1, -2), Point::new(-3, 5));
+        assert_eq!(a.manhattan_distance(b), 11);
+        assert_eq!(a.chebyshev_distance(b), 7);
+        assert_eq!(a.manhattan_distance(a), 0);
+    }
+
+    #[test]
+    fn test_direction4_rotation() {
+        assert_eq!(Direction4::North.rotate_cw(), Direction4::East);
+        assert_eq!(Direction4::North.rotate_ccw(), Direction4::West);
+        assert_eq!(Direction4::East.reverse(), Direction4::West);
+        for direction in Direction4::ALL {
+            assert_eq!(direction.rotate_cw().rotate_ccw(), direction);
+            assert_eq!(direction.reverse().vector(), -direction.vector());
+        }
+    }
+
+    #[test]
+    fn test_direction8_rotation() {
+        assert_eq!(Direction8::North.rotate_cw(), Direction8::NorthEast);
+        assert_eq!(Direction8::North.rotate_ccw(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthWest.reverse(), Direction8::NorthEast);
+        for direction in Direction8::ALL {
+            assert_eq!(direction.rotate_cw().rotate_ccw(), direction);
+            assert_eq!(direction.reverse().vector(), -direction.vector());
+            assert_ne!(direction.vector(), Vector::new(0, 0));
+        }
+    }
+
+    #[test]
+    fn test_direction8_from_direction4() {
+        for direction in Direction4::ALL {
+            assert_eq!(Direction8::from(direction).vector(), direction.vector());
+        }
+    }
+}
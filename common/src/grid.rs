@@ -0,0 +1,293 @@
+use crate::error::{Error, Result};
+use crate::geometry::{Direction4, Direction8, Point};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+// A rectangular grid of cells, stored row by row. (0, 0) is the top left and
+// y increases downwards, the same way the puzzle input reads
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    // Builds a grid from its cells in row order. Panics if there aren't
+    // exactly width * height of them
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "Grid needs width * height cells"
+        );
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    // Parses one cell per character, one row per line. Every row must be as
+    // wide as the first, and the converter can reject a character by
+    // returning None. Blank lines at the end aren't rows
+    pub fn parse_with<F>(input: &str, convert: F) -> Result<Self>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        let lines: Vec<&str> = input.lines().collect();
+        let end = lines
+            .iter()
+            .rposition(|line| !line.is_empty())
+            .map_or(0, |last| last + 1);
+        for (y, line) in lines[..end].iter().enumerate() {
+            let mut row_width = 0;
+            for (x, c) in line.chars().enumerate() {
+                let cell = convert(c).ok_or_else(|| {
+                    Error::expected(y + 1, x + 1, &format!("a valid cell, not {:?}", c))
+                })?;
+                cells.push(cell);
+                row_width += 1;
+            }
+
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(Error::expected(
+                        y + 1,
+                        row_width.min(width) + 1,
+                        &format!("a row of {} characters", width),
+                    ));
+                }
+                _ => {}
+            }
+            height += 1;
+        }
+
+        Ok(Grid::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0
+            && point.y >= 0
+            && (point.x as usize) < self.width
+            && (point.y as usize) < self.height
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y as usize * self.width + point.x as usize)
+        } else {
+            None
+        }
+    }
+
+    // The cell at a point, or None if the point is off the grid
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|index| &mut self.cells[index])
+    }
+
+    // Every point on the grid, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as i64;
+        (0..self.height as i64).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    // The first point (row by row) whose cell matches
+    pub fn position<F>(&self, predicate: F) -> Option<Point>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.points().find(|&point| predicate(&self[point]))
+    }
+
+    // The orthogonal neighbours of a point that are on the grid, clockwise
+    // from north
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction4::ALL
+            .iter()
+            .map(move |direction| point + direction.vector())
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+
+    // The orthogonal and diagonal neighbours of a point that are on the grid,
+    // clockwise from north
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction8::ALL
+            .iter()
+            .map(move |direction| point + direction.vector())
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(self.height)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point).unwrap_or_else(|| {
+            panic!(
+                "{} is outside the {}x{} grid",
+                point, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{} is outside the {}x{} grid", point, width, height))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        Grid::parse_with(input, Some)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+    }
+
+    #[test]
+    fn test_parse_ragged_rows() {
+        assert_eq!(
+            "abc\nde".parse::<Grid<char>>(),
+            Err(Error::expected(2, 3, "a row of 3 characters"))
+        );
+    }
+
+    #[test]
+    fn test_parse_trailing_blank_lines() {
+        assert_eq!("abc\ndef\n\n\n".parse::<Grid<char>>(), Ok(grid()));
+        assert!("abc\n\ndef\n".parse::<Grid<char>>().is_err());
+        assert_eq!(
+            "\n\n".parse::<Grid<char>>().map(|grid| grid.height()),
+            Ok(0)
+        );
+    }
+
+    #[test]
+    fn test_parse_with_rejects_cell() {
+        let error = Grid::parse_with("12\n3x", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(error, Error::expected(2, 2, "a valid cell, not 'x'"));
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        let grid = "".parse::<Grid<char>>().unwrap();
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.points().count(), 0);
+    }
+
+    #[test]
+    fn test_get_out_of_bounds() {
+        let grid = grid();
+        assert_eq!(grid.get(Point::new(0, 0)), Some(&'a'));
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+    }
+
+    #[test]
+    fn test_neighbours4_at_corner() {
+        let neighbours: Vec<Point> = grid().neighbours4(Point::new(0, 0)).collect();
+        assert_eq!(neighbours, vec![Point::new(1, 0), Point::new(0, 1)]);
+    }
+
+    #[test]
+    fn test_neighbours8_in_middle() {
+        let grid = grid();
+        let neighbours: Vec<char> = grid
+            .neighbours8(Point::new(1, 0))
+            .map(|p| grid[p])
+            .collect();
+        assert_eq!(neighbours, vec!['c', 'f', 'e', 'd', 'a']);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn test_position_and_set() {
+        let mut grid = grid();
+        assert_eq!(grid.position(|&c| c == 'e'), Some(Point::new(1, 1)));
+        grid[Point::new(1, 1)] = '#';
+        assert_eq!(grid.to_string(), "abc\nd#f\n");
+    }
+}
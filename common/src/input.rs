@@ -0,0 +1,90 @@
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+// The filename that means "read the puzzle input from stdin"
+pub const STDIN: &str = "-";
+
+// Opens the puzzle input, which is stdin if the path is "-"
+fn open<P: AsRef<Path>>(path: P) -> io::Result<Box<dyn Read>> {
+    if path.as_ref() == Path::new(STDIN) {
+        Ok(Box::new(io::stdin()))
+    } else {
+        Ok(Box::new(File::open(path)?))
+    }
+}
+
+// Reads every line of the puzzle input into a vector
+pub fn read_lines<P: AsRef<Path>>(path: P) -> io::Result<Vec<String>> {
+    BufReader::new(open(path)?).lines().collect()
+}
+
+// Reads the whole puzzle input as one string
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    let mut input = String::new();
+    open(path)?.read_to_string(&mut input)?;
+    Ok(input)
+}
+
+// How to refer to the input in messages
+pub fn source_name(path: &str) -> &str {
+    if path == STDIN {
+        "<stdin>"
+    } else {
+        path
+    }
+}
+
+// Reads the whole puzzle input, naming the file (or stdin) in any error
+pub fn load(path: &str) -> Result<String> {
+    read_to_string(path).map_err(|e| Error::Io {
+        file: source_name(path).to_string(),
+        message: e.to_string(),
+    })
+}
+
+// Opens the puzzle input to be read a line at a time, for inputs too big to
+// load all at once, naming the file (or stdin) in any error
+pub fn open_lines(path: &str) -> Result<impl Iterator<Item = Result<String>>> {
+    let file = source_name(path).to_string();
+    let reader = open(path).map_err(|e| Error::Io {
+        file: file.clone(),
+        message: e.to_string(),
+    })?;
+    Ok(BufReader::new(reader).lines().map(move |line| {
+        line.map_err(|e| Error::Io {
+            file: file.clone(),
+            message: e.to_string(),
+        })
+    }))
+}
+
+// Reads and parses a day's puzzle input, naming the file in any error
+pub fn parse_file<S: Solution>(path: &str) -> Result<S::Input> {
+    S::parse(&load(path)?).map_err(|e| e.in_file(source_name(path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_missing_file() {
+        assert!(matches!(
+            load("no/such/input.txt"),
+            Err(Error::Io { file, .. }) if file == "no/such/input.txt"
+        ));
+    }
+
+    #[test]
+    fn test_open_lines() {
+        let mut lines = open_lines(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml")).unwrap();
+        assert_eq!(lines.next(), Some(Ok("[package]".to_string())));
+        assert!(matches!(
+            open_lines("no/such/input.txt").err(),
+            Some(Error::Io { file, .. }) if file == "no/such/input.txt"
+        ));
+    }
+}
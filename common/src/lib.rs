@@ -0,0 +1,27 @@
+//! Helpers shared by every day's solution: reading the puzzle input,
+//! parsing the command line and .aoc.toml, printing the answers, reporting
+//! bad input, logging diagnostics, spreading work across threads, a grid for
+//! the map puzzles and the `Solution` trait every day implements.
+
+pub mod args;
+pub mod config;
+pub mod differential;
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod log;
+pub mod output;
+pub mod parallel;
+pub mod random;
+pub mod solution;
+
+pub use args::Args;
+pub use config::Config;
+pub use error::Error;
+pub use geometry::{Direction4, Direction8, Point, Vector};
+pub use grid::Grid;
+pub use output::Format;
+pub use parallel::par_map;
+pub use random::{Generate, Rng};
+pub use solution::{Answer, Part, Solution};
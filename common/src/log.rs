@@ -0,0 +1,121 @@
+use std::env;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// The environment variable that sets the level when no -v flag is given
+pub const LOG_ENV: &str = "AOC_LOG";
+
+// How much diagnostic output to write to stderr. Answers always go to
+// stdout whatever the level, so logging never gets mixed up with them
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    #[default]
+    Off,
+    // What a solver is doing, a line or so per step (-v)
+    Debug,
+    // Everything, down to every cell a solver looks at (-vv)
+    Trace,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+
+impl Level {
+    // The level for a -v style flag, or None if it isn't one
+    pub fn from_flag(flag: &str) -> Option<Level> {
+        match flag {
+            "-v" => Some(Level::Debug),
+            "-vv" => Some(Level::Trace),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "off" | "0" | "" => Ok(Level::Off),
+            "debug" | "1" => Ok(Level::Debug),
+            "trace" | "2" => Ok(Level::Trace),
+            _ => Err(format!(
+                "Invalid log level {:?}, expected off, debug or trace",
+                value
+            )),
+        }
+    }
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+// Sets the level from a -v flag if there was one, otherwise from AOC_LOG,
+// otherwise from the config file. Anything AOC_LOG can't be parsed as is
+// reported and ignored
+pub fn init(flag: Option<Level>, configured: Option<Level>) {
+    let configured = configured.unwrap_or_default();
+    let level = flag.unwrap_or_else(|| match env::var(LOG_ENV) {
+        Ok(value) => value.parse().unwrap_or_else(|message| {
+            eprintln!("warning: {}: {}", LOG_ENV, message);
+            configured
+        }),
+        Err(_) => configured,
+    });
+    set_level(level);
+}
+
+// Writes a line to stderr when running with -v or more
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Writes a line to stderr when running with -vv
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_from_flag() {
+        assert_eq!(Level::from_flag("-v"), Some(Level::Debug));
+        assert_eq!(Level::from_flag("-vv"), Some(Level::Trace));
+        assert_eq!(Level::from_flag("-x"), None);
+    }
+
+    #[test]
+    fn test_level_from_str() {
+        assert_eq!("TRACE".parse(), Ok(Level::Trace));
+        assert_eq!("1".parse(), Ok(Level::Debug));
+        assert_eq!("off".parse(), Ok(Level::Off));
+        assert!("loud".parse::<Level>().is_err());
+    }
+
+    #[test]
+    fn test_enabled() {
+        // The level is global, so this is the only test that changes it
+        set_level(Level::Debug);
+        assert!(enabled(Level::Debug));
+        assert!(!enabled(Level::Trace));
+        assert!(!enabled(Level::Off));
+        set_level(Level::Off);
+        assert!(!enabled(Level::Debug));
+    }
+}
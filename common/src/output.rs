@@ -0,0 +1,153 @@
+use crate::error;
+use crate::solution::{Answer, Part, Solution};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+// How answers are printed: the day's own wording, or one JSON object per
+// line for scripts and dashboards
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format: {} (expected text or json)", value)),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Text => f.pad("text"),
+            Format::Json => f.pad("json"),
+        }
+    }
+}
+
+// Prints an answer with the label the day uses for it, e.g. "Part two sum: 31"
+pub fn print_answer<T: Display>(label: &str, answer: T) {
+    println!("{}: {}", label, answer);
+}
+
+// Prints one part's answer in the requested format. The label is only used
+// for text, JSON always has the same keys
+pub fn print_result(
+    format: Format,
+    label: &str,
+    day: u8,
+    part: Part,
+    answer: &Answer,
+    elapsed: Duration,
+) {
+    match format {
+        Format::Text => print_answer(label, answer),
+        Format::Json => println!("{}", to_json(day, part, answer, elapsed)),
+    }
+}
+
+// Solves one part of an already parsed input, timing it, and prints the result
+pub fn print_part<S: Solution>(
+    format: Format,
+    input: &S::Input,
+    part: Part,
+    label: &str,
+) -> error::Result<()> {
+    print_solved(format, label, S::DAY, part, || S::run(input, part))
+}
+
+// Times `solve` and prints its answer as the given part, for a day solving a
+// variation on its puzzle, e.g. day4 searching for another word. Nothing is
+// printed if it fails
+pub fn print_solved<F: FnOnce() -> error::Result<Answer>>(
+    format: Format,
+    label: &str,
+    day: u8,
+    part: Part,
+    solve: F,
+) -> error::Result<()> {
+    let start = Instant::now();
+    let answer = solve()?;
+    print_result(format, label, day, part, &answer, start.elapsed());
+    Ok(())
+}
+
+// {"day":N,"part":P,"answer":...,"elapsed_ns":...} - numbers stay numbers,
+// anything else is a JSON string
+pub fn to_json(day: u8, part: Part, answer: &Answer, elapsed: Duration) -> String {
+    let answer = match answer {
+        Answer::Int(value) => value.to_string(),
+        Answer::Text(value) => json_string(value),
+    };
+    format!(
+        "{{\"day\":{},\"part\":{},\"answer\":{},\"elapsed_ns\":{}}}",
+        day,
+        part,
+        answer,
+        elapsed.as_nanos()
+    )
+}
+
+fn json_string(value: &str) -> String {
+    let mut json = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert_eq!("text".parse::<Format>(), Ok(Format::Text));
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_to_json_number() {
+        assert_eq!(
+            to_json(
+                6,
+                Part::Two,
+                &Answer::from(41u32),
+                Duration::from_nanos(1500)
+            ),
+            r#"{"day":6,"part":2,"answer":41,"elapsed_ns":1500}"#
+        );
+    }
+
+    #[test]
+    fn test_to_json_text_is_escaped() {
+        assert_eq!(
+            to_json(
+                1,
+                Part::One,
+                &Answer::from(String::from("a\"b\\c\n")),
+                Duration::ZERO
+            ),
+            r#"{"day":1,"part":1,"answer":"a\"b\\c\n","elapsed_ns":0}"#
+        );
+    }
+}
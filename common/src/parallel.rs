@@ -0,0 +1,95 @@
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+// How many threads par_map uses. Zero means it hasn't been set, so every
+// core is used
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+// Sets the number of threads from --threads. One runs everything on the
+// calling thread, which makes timings and debugging simpler
+pub fn set_threads(threads: NonZeroUsize) {
+    THREADS.store(threads.get(), Ordering::Relaxed);
+}
+
+// Goes back to using every core, as if --threads had never been given
+pub fn reset_threads() {
+    THREADS.store(0, Ordering::Relaxed);
+}
+
+pub fn threads() -> usize {
+    match THREADS.load(Ordering::Relaxed) {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        threads => threads,
+    }
+}
+
+// Maps every item on as many threads as --threads allows. Each thread takes
+// one contiguous run of the items and the results are put back together in
+// the items' order, so the output is the same however many threads there are
+pub fn par_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    par_map_on(threads(), items, f)
+}
+
+fn par_map_on<T, R, F>(threads: usize, items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    if threads <= 1 || items.len() <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let chunk = items.len().div_ceil(threads);
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<R>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e))
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_map_keeps_order() {
+        let items: Vec<u64> = (0..1000).collect();
+        let expected: Vec<u64> = items.iter().map(|n| n * n).collect();
+        for threads in [1, 2, 3, 7, 64, 2000] {
+            assert_eq!(par_map_on(threads, &items, |n| n * n), expected);
+        }
+    }
+
+    #[test]
+    fn test_par_map_empty() {
+        assert_eq!(par_map_on(4, &[] as &[u8], |n| *n), Vec::<u8>::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "bad item")]
+    fn test_par_map_passes_on_panics() {
+        par_map_on(4, &[1, 2, 3, 4], |&n| {
+            if n == 3 {
+                panic!("bad item");
+            }
+            n
+        });
+    }
+}
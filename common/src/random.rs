@@ -0,0 +1,106 @@
+use std::ops::Range;
+
+// A small, seeded random number generator (SplitMix64) for generating puzzle
+// inputs. The same seed always gives the same sequence, on every platform, so
+// a failing input can be reproduced from its seed alone
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // A number in the range, which must not be empty
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(!range.is_empty(), "Can't pick from an empty range");
+        let span = range.end.abs_diff(range.start);
+        range.start.wrapping_add((self.next_u64() % span) as i64)
+    }
+
+    // An index into something of length `len`, which must not be zero
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..len as i64) as usize
+    }
+
+    // True with the given probability
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+// Generates random puzzle inputs for a day, for fuzzing its parser and for
+// comparing its solutions against each other. `size` is how big the input is
+// in whatever unit suits the day: lines, reports or the side of a grid
+pub trait Generate {
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+        let a: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+        let b: Vec<u64> = (0..10).map(|_| b.next_u64()).collect();
+        assert_eq!(a, b);
+        assert_ne!(
+            a,
+            (0..10).map(|_| Rng::new(43).next_u64()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_known_sequence() {
+        // The first output of SplitMix64 seeded with 0
+        assert_eq!(Rng::new(0).next_u64(), 0xe220_a839_7b1d_cdaf);
+    }
+
+    #[test]
+    fn test_range_stays_in_bounds() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            assert!((-3..4).contains(&rng.range(-3..4)));
+        }
+        assert_eq!(rng.range(i64::MIN..i64::MIN + 1), i64::MIN);
+    }
+
+    #[test]
+    fn test_shuffle_keeps_items() {
+        let mut items: Vec<u32> = (0..20).collect();
+        Rng::new(7).shuffle(&mut items);
+        let mut sorted = items.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+        assert_ne!(items, sorted);
+    }
+
+    #[test]
+    fn test_chance_extremes() {
+        let mut rng = Rng::new(3);
+        assert!((0..100).all(|_| rng.chance(1.0)));
+        assert!((0..100).all(|_| !rng.chance(0.0)));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::differential::Variant;
+use crate::error;
+use std::fmt;
+
+// The answer to one part of a puzzle. Most are numbers, but keeping the
+// variants lets a day answer with text if the puzzle asks for it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Int(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, i64, i128, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(value) => value.fmt(f),
+            Answer::Text(value) => f.pad(value),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl TryFrom<u8> for Part {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(format!("Invalid part: {}", value)),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+// Every day implements this so its parts can be run the same way by the
+// day's own binary, the aoc runner and tests
+pub trait Solution {
+    // The day of December the puzzle was set on
+    const DAY: u8;
+
+    type Input;
+
+    fn parse(input: &str) -> error::Result<Self::Input>;
+    // Either part may fail if its answer is too big to work out, even
+    // though the input parsed
+    fn part1(input: &Self::Input) -> error::Result<Answer>;
+    fn part2(input: &Self::Input) -> error::Result<Answer>;
+
+    // Solves the given part of an already parsed input
+    fn run(input: &Self::Input, part: Part) -> error::Result<Answer> {
+        match part {
+            Part::One => Self::part1(input),
+            Part::Two => Self::part2(input),
+        }
+    }
+
+    // Parses the input and solves the given part
+    fn solve(input: &str, part: Part) -> error::Result<Answer> {
+        Self::run(&Self::parse(input)?, part)
+    }
+
+    // Parses an in-memory input and solves both parts, so callers (and tests)
+    // don't need to go through a file
+    fn solve_str(input: &str) -> error::Result<[Answer; 2]> {
+        let input = Self::parse(input)?;
+        Ok([Self::run(&input, Part::One)?, Self::run(&input, Part::Two)?])
+    }
+
+    // Faster (or just different) implementations of the parts, paired with
+    // the ones they should agree with, for `differential::compare` to check.
+    // Most days only have the one way of solving each part
+    fn variants() -> Vec<Variant<Self::Input>> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct LineCount;
+
+    impl Solution for LineCount {
+        const DAY: u8 = 0;
+
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> error::Result<Self::Input> {
+            Ok(input.lines().map(String::from).collect())
+        }
+
+        fn part1(input: &Self::Input) -> error::Result<Answer> {
+            Ok(input.len().into())
+        }
+
+        fn part2(input: &Self::Input) -> error::Result<Answer> {
+            Ok(input.concat().into())
+        }
+    }
+
+    #[test]
+    fn test_solve_dispatches_to_part() {
+        assert_eq!(LineCount::solve("a\nb", Part::One), Ok(Answer::Int(2)));
+        assert_eq!(
+            LineCount::solve("a\nb", Part::Two),
+            Ok(Answer::Text("ab".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_solve_str_solves_both_parts() {
+        assert_eq!(
+            LineCount::solve_str("a\nb\nc"),
+            Ok([Answer::Int(3), Answer::Text("abc".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(-3).to_string(), "-3");
+        assert_eq!(Answer::from(String::from("abc")).to_string(), "abc");
+        assert_eq!(format!("{:>4}", Answer::from(7u32)), "   7");
+    }
+
+    #[test]
+    fn test_part_try_from() {
+        assert_eq!(Part::try_from(2), Ok(Part::Two));
+        assert!(Part::try_from(3).is_err());
+    }
+}
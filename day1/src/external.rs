@@ -0,0 +1,377 @@
+use crate::id::{Id, Number};
+use crate::layout::Layout;
+use aoc_common::error::{self, Error};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::marker::PhantomData;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// How many runs are merged at once. Any more are first merged into longer
+// runs, so as not to run out of file handles
+const FAN_IN: usize = 64;
+
+// Spill files made so far by this process, to keep their names apart
+static SPILLED: AtomicUsize = AtomicUsize::new(0);
+
+// Sorts lists too big to hold in memory. The IDs are read `run_len` at a
+// time, and each run is sorted and written to a temporary file. Both answers
+// are then worked out by merging the runs back together as they're read,
+// with the same checked arithmetic as ListPair
+#[derive(Clone, Debug)]
+pub struct ExternalSort<T: Id = i64> {
+    dir: PathBuf,
+    run_len: usize,
+    fan_in: usize,
+    ids: PhantomData<T>,
+}
+
+// Both lists, sorted into runs on disk. The files are deleted when this is
+// dropped
+#[derive(Debug)]
+pub struct SortedLists<T: Id = i64> {
+    left: Vec<Run>,
+    right: Vec<Run>,
+    ids: PhantomData<T>,
+}
+
+// A sorted run of IDs in a temporary file, which goes when the run does
+#[derive(Debug)]
+struct Run {
+    path: PathBuf,
+    len: usize,
+}
+
+// One list's IDs waiting to be sorted, and the runs written out so far
+struct Spill<'a, T: Id> {
+    sort: &'a ExternalSort<T>,
+    buffer: Vec<T>,
+    runs: Vec<Run>,
+}
+
+// Several sorted runs read back as one sorted stream, by always taking the
+// smallest of the next ID from each
+struct Merge<'a, T: Id> {
+    readers: Vec<(BufReader<File>, &'a Path)>,
+    heap: BinaryHeap<Reverse<(T, usize)>>,
+}
+
+impl<T: Id> ExternalSort<T> {
+    // Spills to the system's temporary directory
+    pub fn new(run_len: NonZeroUsize) -> Self {
+        ExternalSort {
+            dir: env::temp_dir(),
+            run_len: run_len.get(),
+            fan_in: FAN_IN,
+            ids: PhantomData,
+        }
+    }
+
+    // Spills somewhere else, e.g. a disk with more room than the temporary
+    // directory
+    pub fn in_dir<P: Into<PathBuf>>(self, dir: P) -> Self {
+        ExternalSort {
+            dir: dir.into(),
+            ..self
+        }
+    }
+
+    // Reads the lists laid out as `layout` says, a line at a time, sorting
+    // them into runs on disk as it goes
+    pub fn read<L: AsRef<str>>(
+        &self,
+        lines: impl IntoIterator<Item = error::Result<L>>,
+        layout: &Layout,
+    ) -> error::Result<SortedLists<T>> {
+        let mut left = Spill::new(self);
+        let mut right = Spill::new(self);
+        layout.read_lines(lines, |l, r| {
+            left.push(l)?;
+            right.push(r)
+        })?;
+        Ok(SortedLists {
+            left: left.finish()?,
+            right: right.finish()?,
+            ids: PhantomData,
+        })
+    }
+}
+
+impl<T: Id> SortedLists<T> {
+    pub fn left_len(&self) -> usize {
+        self.left.iter().map(|run| run.len).sum()
+    }
+
+    pub fn right_len(&self) -> usize {
+        self.right.iter().map(|run| run.len).sum()
+    }
+
+    // Pairs up the sorted lists, smallest with smallest, as ListPair does.
+    // Any extra IDs in the longer list are left out
+    pub fn total_distance(&self) -> error::Result<T::Distance> {
+        let mut left = Merge::<T>::new(&self.left)?;
+        let mut right = Merge::<T>::new(&self.right)?;
+        let mut total = T::Distance::default();
+        while let (Some(l), Some(r)) = (left.next()?, right.next()?) {
+            total = total
+                .checked_add(l.distance(r))
+                .ok_or_else(|| Error::overflow("the total distance", T::Distance::NAME))?;
+        }
+        Ok(total)
+    }
+
+    // Walks both sorted lists together, so each ID's count on the right is
+    // known once its copies on the left have all been seen
+    pub fn similarity_score(&self) -> error::Result<T> {
+        let mut left = Merge::<T>::new(&self.left)?;
+        let mut right = Merge::<T>::new(&self.right)?;
+        let mut total = T::default();
+        let mut next_left = left.next()?;
+        let mut next_right = right.next()?;
+        while let Some(id) = next_left {
+            let mut ours = 0;
+            while next_left == Some(id) {
+                ours += 1;
+                next_left = left.next()?;
+            }
+            while next_right.is_some_and(|r| r < id) {
+                next_right = right.next()?;
+            }
+            let mut theirs = 0;
+            while next_right == Some(id) {
+                theirs += 1;
+                next_right = right.next()?;
+            }
+
+            let score = id
+                .times(theirs)
+                .ok_or_else(|| Error::overflow(&format!("the score for {}", id), T::NAME))?;
+            total = score
+                .times(ours)
+                .and_then(|scores| total.checked_add(scores))
+                .ok_or_else(|| Error::overflow("the similarity score", T::NAME))?;
+        }
+        Ok(total)
+    }
+}
+
+impl<'a, T: Id> Spill<'a, T> {
+    fn new(sort: &'a ExternalSort<T>) -> Self {
+        Spill {
+            sort,
+            buffer: Vec::new(),
+            runs: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, id: T) -> error::Result<()> {
+        self.buffer.push(id);
+        if self.buffer.len() >= self.sort.run_len {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> error::Result<()> {
+        if !self.buffer.is_empty() {
+            self.buffer.sort_unstable();
+            let run = Run::write(&self.sort.dir, self.buffer.iter().map(|&id| Ok(id)))?;
+            self.runs.push(run);
+            self.buffer.clear();
+        }
+        Ok(())
+    }
+
+    // Writes out what's left, then merges runs until few enough are left to
+    // merge in one go
+    fn finish(mut self) -> error::Result<Vec<Run>> {
+        self.flush()?;
+        let mut runs = self.runs;
+        while runs.len() > self.sort.fan_in {
+            let mut merged = Vec::new();
+            for group in runs.chunks(self.sort.fan_in) {
+                let mut merge = Merge::<T>::new(group)?;
+                merged.push(Run::write(
+                    &self.sort.dir,
+                    std::iter::from_fn(|| merge.next().transpose()),
+                )?);
+            }
+            runs = merged;
+        }
+        Ok(runs)
+    }
+}
+
+impl Run {
+    // Writes the IDs, which must already be sorted, to a new file in `dir`
+    fn write<T: Id>(dir: &Path, ids: impl Iterator<Item = error::Result<T>>) -> error::Result<Run> {
+        let name = format!(
+            "aoc-day1-{}-{}.run",
+            process::id(),
+            SPILLED.fetch_add(1, Ordering::Relaxed)
+        );
+        // Made before the file, so that it's cleaned up if writing fails
+        let mut run = Run {
+            path: dir.join(name),
+            len: 0,
+        };
+        let file = File::create(&run.path).map_err(|e| io_error(&run.path, e))?;
+        let mut writer = BufWriter::new(file);
+        for id in ids {
+            id?.write_to(&mut writer)
+                .map_err(|e| io_error(&run.path, e))?;
+            run.len += 1;
+        }
+        writer.flush().map_err(|e| io_error(&run.path, e))?;
+        Ok(run)
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+impl<'a, T: Id> Merge<'a, T> {
+    fn new(runs: &'a [Run]) -> error::Result<Self> {
+        let mut merge = Merge {
+            readers: Vec::new(),
+            heap: BinaryHeap::new(),
+        };
+        for run in runs {
+            let file = File::open(&run.path).map_err(|e| io_error(&run.path, e))?;
+            merge.readers.push((BufReader::new(file), &run.path));
+            merge.refill(merge.readers.len() - 1)?;
+        }
+        Ok(merge)
+    }
+
+    fn next(&mut self) -> error::Result<Option<T>> {
+        match self.heap.pop() {
+            Some(Reverse((id, run))) => {
+                self.refill(run)?;
+                Ok(Some(id))
+            }
+            None => Ok(None),
+        }
+    }
+
+    // Queues the next ID from a run, if it has any left
+    fn refill(&mut self, run: usize) -> error::Result<()> {
+        let (reader, path) = &mut self.readers[run];
+        match T::read_from(reader) {
+            Ok(id) => self.heap.push(Reverse((id, run))),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {}
+            Err(e) => return Err(io_error(path, e)),
+        }
+        Ok(())
+    }
+}
+
+fn io_error(path: &Path, e: io::Error) -> Error {
+    Error::Io {
+        file: path.display().to_string(),
+        message: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ListPair;
+    use aoc_common::Rng;
+
+    // A directory of its own for each test, so they can check it's emptied
+    fn spill_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-day1-{}-{}", name, process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn sort(run_len: usize, dir: &Path) -> ExternalSort {
+        ExternalSort::new(NonZeroUsize::new(run_len).unwrap()).in_dir(dir)
+    }
+
+    #[test]
+    fn test_example() {
+        let dir = spill_dir("example");
+        let input = include_str!("../test.txt");
+        let lists = sort(4, &dir)
+            .read(input.lines().map(Ok), &Layout::default())
+            .unwrap();
+        assert_eq!((lists.left_len(), lists.right_len()), (6, 6));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 4);
+        assert_eq!(lists.total_distance(), Ok(11));
+        assert_eq!(lists.similarity_score(), Ok(31));
+
+        drop(lists);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        fs::remove_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_agrees_with_list_pair() {
+        let dir = spill_dir("random");
+        let mut rng = Rng::new(25);
+        for len in [0, 1, 10, 500] {
+            let pairs: Vec<(i64, i64)> = (0..len)
+                .map(|_| (rng.range(-50..50), rng.range(-50..50)))
+                .collect();
+            let input: String = pairs
+                .iter()
+                .map(|(l, r)| format!("{} {}\n", l, r))
+                .collect();
+            let expected = ListPair::from_iter(pairs);
+
+            // A small fan-in so the runs need merging more than once
+            let mut sort = sort(3, &dir);
+            sort.fan_in = 4;
+            let lists = sort
+                .read(input.lines().map(Ok), &Layout::default())
+                .unwrap();
+            assert!(lists.left.len() <= 4);
+            assert_eq!(lists.total_distance(), expected.total_distance());
+            assert_eq!(lists.similarity_score(), expected.similarity_score());
+        }
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        fs::remove_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_wider_ids() {
+        let dir = spill_dir("wide");
+        let big = u64::MAX as u128 + 1;
+        let input = format!("{0} {0}\n{0} 0\n", big);
+        let lists = ExternalSort::<u128>::new(NonZeroUsize::new(1).unwrap())
+            .in_dir(&dir)
+            .read(input.lines().map(Ok), &Layout::default())
+            .unwrap();
+        let expected: ListPair<u128> = input.parse().unwrap();
+        assert_eq!(lists.total_distance(), expected.total_distance());
+        assert_eq!(lists.similarity_score(), Ok(2 * big));
+        drop(lists);
+        fs::remove_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_overflow_is_an_error() {
+        let dir = spill_dir("overflow");
+        let input = format!("{0} {0}\n{0} 1\n", i64::MAX);
+        let lists = sort(1, &dir)
+            .read(input.lines().map(Ok), &Layout::default())
+            .unwrap();
+        assert_eq!(lists.total_distance(), Ok(i64::MAX as u64 - 1));
+        assert_eq!(
+            lists.similarity_score(),
+            Err(Error::overflow("the similarity score", "i64"))
+        );
+        drop(lists);
+        fs::remove_dir(&dir).unwrap();
+    }
+}
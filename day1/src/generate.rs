@@ -0,0 +1,35 @@
+use crate::Day1;
+use aoc_common::{Generate, Rng};
+
+impl Generate for Day1 {
+    // `size` pairs of five digit location IDs, like the real input. About a
+    // third of the right hand IDs are copied from the left so the similarity
+    // score has something to count
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let left: Vec<i64> = (0..size).map(|_| rng.range(10000..100000)).collect();
+        left.iter()
+            .map(|&l| {
+                let r = if rng.chance(0.3) {
+                    *rng.choose(&left)
+                } else {
+                    rng.range(10000..100000)
+                };
+                format!("{}   {}\n", l, r)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solution;
+
+    #[test]
+    fn test_generate_parses() {
+        let input = Day1::generate(&mut Rng::new(1), 50);
+        let lists = Day1::parse(&input).unwrap();
+        assert_eq!((lists.left().len(), lists.right().len()), (50, 50));
+        assert_eq!(input, Day1::generate(&mut Rng::new(1), 50));
+    }
+}
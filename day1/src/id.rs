@@ -0,0 +1,155 @@
+use aoc_common::error::{self, Error};
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::io::{self, Read, Write};
+use std::str::FromStr;
+
+// An integer type that totals can be added up in without wrapping
+pub trait Number: Copy + Ord + Hash + Debug + Display + Default + FromStr {
+    // The type's name, for overflow errors
+    const NAME: &'static str;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+// An integer type location IDs can be read as. Distances between two IDs
+// always fit in the unsigned type of the same width, but an ID weighted by
+// its count might not fit in the ID's own type
+pub trait Id: Number {
+    type Distance: Number;
+
+    fn distance(self, other: Self) -> Self::Distance;
+    fn times(self, count: usize) -> Option<Self>;
+
+    // Writes the ID to, or reads it back from, a file of IDs in binary
+    fn write_to<W: Write>(self, writer: &mut W) -> io::Result<()>;
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self>;
+}
+
+// An ID type narrow enough that running totals over it, like an ID times a
+// count or the width of a gap between IDs, fit exactly in an i128. That's
+// every type up to 64 bits
+pub trait NarrowId: Id {
+    fn wide(self) -> i128;
+
+    // A total back in the ID's own width, or its distance's, if it fits
+    fn narrow(total: i128) -> Option<Self>;
+    fn narrow_distance(total: i128) -> Option<Self::Distance>;
+}
+
+macro_rules! number {
+    ($($t:ty),*) => {
+        $(
+            impl Number for $t {
+                const NAME: &'static str = stringify!($t);
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! id {
+    ($($t:ty => $distance:ty),*) => {
+        $(
+            impl Id for $t {
+                type Distance = $distance;
+
+                fn distance(self, other: Self) -> $distance {
+                    self.abs_diff(other)
+                }
+
+                fn times(self, count: usize) -> Option<Self> {
+                    if self == 0 {
+                        return Some(0);
+                    }
+                    <$t>::try_from(count).ok()?.checked_mul(self)
+                }
+
+                fn write_to<W: Write>(self, writer: &mut W) -> io::Result<()> {
+                    writer.write_all(&self.to_le_bytes())
+                }
+
+                fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+                    let mut bytes = [0; std::mem::size_of::<$t>()];
+                    reader.read_exact(&mut bytes)?;
+                    Ok(<$t>::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! narrow_id {
+    ($($t:ty),*) => {
+        $(
+            impl NarrowId for $t {
+                fn wide(self) -> i128 {
+                    i128::from(self)
+                }
+
+                fn narrow(total: i128) -> Option<Self> {
+                    Self::try_from(total).ok()
+                }
+
+                fn narrow_distance(total: i128) -> Option<Self::Distance> {
+                    Self::Distance::try_from(total).ok()
+                }
+            }
+        )*
+    };
+}
+
+number!(i32, i64, i128, u32, u64, u128);
+id!(i32 => u32, i64 => u64, i128 => u128, u32 => u32, u64 => u64, u128 => u128);
+narrow_id!(i32, i64, u32, u64);
+
+// Adds up `values`, stopping with an error naming `what` was being added up
+// if a running total doesn't fit in `N`
+pub fn checked_sum<N: Number>(values: impl IntoIterator<Item = N>, what: &str) -> error::Result<N> {
+    values
+        .into_iter()
+        .try_fold(N::default(), N::checked_add)
+        .ok_or_else(|| Error::overflow(what, N::NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_fits_unsigned() {
+        assert_eq!(i64::MIN.distance(i64::MAX), u64::MAX);
+        assert_eq!(3u32.distance(7), 4);
+    }
+
+    #[test]
+    fn test_times() {
+        assert_eq!(3i32.times(4), Some(12));
+        assert_eq!(i32::MAX.times(2), None);
+        assert_eq!(0u32.times(usize::MAX), Some(0));
+        assert_eq!(u128::MAX.times(1), Some(u128::MAX));
+    }
+
+    #[test]
+    fn test_write_and_read_back() {
+        let mut bytes = Vec::new();
+        (-5i64).write_to(&mut bytes).unwrap();
+        u128::MAX.write_to(&mut bytes).unwrap();
+        let mut reader = &bytes[..];
+        assert_eq!(i64::read_from(&mut reader).unwrap(), -5);
+        assert_eq!(u128::read_from(&mut reader).unwrap(), u128::MAX);
+        assert!(u32::read_from(&mut reader).is_err());
+    }
+
+    #[test]
+    fn test_checked_sum() {
+        assert_eq!(checked_sum([1u64, 2, 3], "the sum"), Ok(6));
+        assert_eq!(
+            checked_sum([u64::MAX, 1], "the sum"),
+            Err(Error::overflow("the sum", "u64"))
+        );
+    }
+}
@@ -0,0 +1,629 @@
+use crate::id::{NarrowId, Number};
+use crate::ListPair;
+use aoc_common::error::{self, Error};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+// Blocks hold around the square root of the number of segments, but never
+// fewer than this, as tiny blocks cost more to walk than they save
+const MIN_BLOCK: usize = 16;
+
+// Which list an ID is added to or taken from
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    fn index(self) -> usize {
+        match self {
+            Side::Left => 0,
+            Side::Right => 1,
+        }
+    }
+}
+
+// Day 1's lists for when IDs keep arriving and being retracted, with the
+// total distance and similarity score kept up to date as they do.
+//
+// The total distance of two sorted lists of the same length is the area
+// between their cumulative counts: the sum over every x of |L(x) - R(x)|,
+// where L(x) and R(x) count the IDs at or below x in each list. Between two
+// neighbouring IDs the difference doesn't change, so the number line is cut
+// into segments at every ID present, each with its width and difference.
+// Adding a left ID adds one to the difference of every segment from it
+// onwards (and a right ID takes one away), which the blocks below do in
+// O(sqrt n) time.
+//
+// When one list is longer, the area stops at the largest ID present. That is
+// the distance the sorted lists would have if the shorter one were padded
+// with copies of that largest ID. ListPair leaves the longer list's extra
+// IDs out instead, and those are its largest ones, so what they add when
+// paired with the padding is taken off again when the distance is asked for.
+//
+// IDs and totals are kept as i128s, which is exact for IDs up to 64 bits,
+// and only have to fit the ID's own width when they're handed back.
+#[derive(Clone, Debug)]
+pub struct IncrementalListPair<T: NarrowId = i64> {
+    // How many times each ID is in the left and right lists
+    counts: HashMap<i128, (usize, usize)>,
+    // The segments in ID order, split into blocks
+    blocks: Vec<Block>,
+    segments: usize,
+    left_len: usize,
+    right_len: usize,
+    distance: i128,
+    similarity: i128,
+    ids: PhantomData<T>,
+}
+
+// A run of segments. Each one starts at an ID and reaches to the next
+// segment's start, except the very last one, which has no width
+#[derive(Clone, Debug, Default)]
+struct Block {
+    starts: Vec<i128>,
+    widths: Vec<u128>,
+    // The difference in each segment, less `add`
+    diffs: Vec<i64>,
+    // Added to every difference in the block, so a whole block can be
+    // shifted at once
+    add: i64,
+    // The total width of the segments with each stored difference
+    by_diff: Histogram,
+    // The width of the segments whose difference is zero or more, and of
+    // every segment
+    nonnegative: u128,
+    width: u128,
+    // How many IDs the block's segments start at in each list, and their sum
+    tallies: [(usize, i128); 2],
+}
+
+// Widths by difference. Neighbouring segments' differences are usually
+// only one or two apart, so a block's differences fit in a short run of
+// values, where a vector beats a hash map
+#[derive(Clone, Debug, Default)]
+struct Histogram {
+    first: i64,
+    widths: Vec<u128>,
+}
+
+impl Histogram {
+    fn get(&self, diff: i64) -> u128 {
+        usize::try_from(diff - self.first)
+            .ok()
+            .and_then(|i| self.widths.get(i))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn add(&mut self, diff: i64, width: u128) {
+        if self.widths.is_empty() {
+            self.first = diff;
+        } else if diff < self.first {
+            let missing = (self.first - diff) as usize;
+            self.widths.splice(0..0, std::iter::repeat_n(0, missing));
+            self.first = diff;
+        }
+        let i = (diff - self.first) as usize;
+        if i >= self.widths.len() {
+            self.widths.resize(i + 1, 0);
+        }
+        self.widths[i] += width;
+    }
+
+    fn remove(&mut self, diff: i64, width: u128) {
+        self.widths[(diff - self.first) as usize] -= width;
+    }
+}
+
+impl Block {
+    // A block of (start, width, difference) segments, with each start's
+    // counts looked up in `counts`
+    fn new(segments: &[(i128, u128, i64)], counts: &HashMap<i128, (usize, usize)>) -> Self {
+        let mut block = Block::default();
+        for &(start, width, diff) in segments {
+            block.starts.push(start);
+            block.widths.push(width);
+            block.diffs.push(diff);
+            block.count(width, diff);
+            let (left, right) = counts[&start];
+            block.tally(Side::Left, start, left as i128);
+            block.tally(Side::Right, start, right as i128);
+        }
+        block
+    }
+
+    fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    // Every segment, with its actual difference
+    fn segments(&self) -> impl Iterator<Item = (i128, u128, i64)> + '_ {
+        (0..self.len()).map(|i| (self.starts[i], self.widths[i], self.diffs[i] + self.add))
+    }
+
+    // Adds `copies` of an ID to one list's tally, or takes them away
+    fn tally(&mut self, side: Side, id: i128, copies: i128) {
+        let (count, sum) = &mut self.tallies[side.index()];
+        *count = (*count as i128 + copies) as usize;
+        *sum += id * copies;
+    }
+
+    // Adds a segment with a stored difference to the totals
+    fn count(&mut self, width: u128, diff: i64) {
+        if width == 0 {
+            return;
+        }
+        self.by_diff.add(diff, width);
+        if diff + self.add >= 0 {
+            self.nonnegative += width;
+        }
+        self.width += width;
+    }
+
+    // Takes a segment with a stored difference out of the totals
+    fn uncount(&mut self, width: u128, diff: i64) {
+        if width == 0 {
+            return;
+        }
+        self.by_diff.remove(diff, width);
+        if diff + self.add >= 0 {
+            self.nonnegative -= width;
+        }
+        self.width -= width;
+    }
+
+    // What segment i adds to the distance
+    fn cost(&self, i: usize) -> i128 {
+        self.widths[i] as i128 * (self.diffs[i] + self.add).abs() as i128
+    }
+
+    // Moves segment i to a new width and stored difference, returning how
+    // much that changes the distance by
+    fn update(&mut self, i: usize, width: u128, diff: i64) -> i128 {
+        let before = self.cost(i);
+        self.uncount(self.widths[i], self.diffs[i]);
+        self.widths[i] = width;
+        self.diffs[i] = diff;
+        self.count(width, diff);
+        self.cost(i) - before
+    }
+
+    // Adds one to, or takes one from, every difference in the block,
+    // returning how much that changes the distance by
+    fn shift(&mut self, delta: i64) -> i128 {
+        let width = self.width as i128;
+        let nonnegative = self.nonnegative as i128;
+        let zero = self.by_diff.get(-self.add);
+        let change = if delta > 0 {
+            // Minus one becomes zero
+            self.nonnegative += self.by_diff.get(-1 - self.add);
+            nonnegative - (width - nonnegative)
+        } else {
+            // Zero becomes minus one
+            self.nonnegative -= zero;
+            let zero = zero as i128;
+            (width - nonnegative + zero) - (nonnegative - zero)
+        };
+        self.add += delta;
+        change
+    }
+}
+
+impl<T: NarrowId> Default for IncrementalListPair<T> {
+    fn default() -> Self {
+        IncrementalListPair {
+            counts: HashMap::new(),
+            blocks: Vec::new(),
+            segments: 0,
+            left_len: 0,
+            right_len: 0,
+            distance: 0,
+            similarity: 0,
+            ids: PhantomData,
+        }
+    }
+}
+
+impl<T: NarrowId> IncrementalListPair<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert_left(&mut self, id: T) {
+        self.insert(id.wide(), Side::Left);
+    }
+
+    pub fn insert_right(&mut self, id: T) {
+        self.insert(id.wide(), Side::Right);
+    }
+
+    // Takes one copy of the ID out of the left list, returning false if
+    // there wasn't one
+    pub fn remove_left(&mut self, id: T) -> bool {
+        self.remove(id.wide(), Side::Left)
+    }
+
+    pub fn remove_right(&mut self, id: T) -> bool {
+        self.remove(id.wide(), Side::Right)
+    }
+
+    pub fn left_len(&self) -> usize {
+        self.left_len
+    }
+
+    pub fn right_len(&self) -> usize {
+        self.right_len
+    }
+
+    // The distance ListPair would give, with any extra IDs in the longer
+    // list left out
+    pub fn total_distance(&self) -> error::Result<T::Distance> {
+        let (side, extra) = match self.left_len.checked_sub(self.right_len) {
+            Some(extra) => (Side::Left, extra),
+            None => (Side::Right, self.right_len - self.left_len),
+        };
+        let mut distance = self.distance;
+        if extra > 0 {
+            // They were each paired with the largest ID, which starts the
+            // last segment
+            let largest = *self
+                .blocks
+                .last()
+                .and_then(|block| block.starts.last())
+                .unwrap();
+            distance -= extra as i128 * largest - self.largest_sum(side, extra);
+        }
+        T::narrow_distance(distance)
+            .ok_or_else(|| Error::overflow("the total distance", T::Distance::NAME))
+    }
+
+    pub fn similarity_score(&self) -> error::Result<T> {
+        T::narrow(self.similarity).ok_or_else(|| Error::overflow("the similarity score", T::NAME))
+    }
+
+    // The sum of the `n` largest IDs in one list
+    fn largest_sum(&self, side: Side, mut n: usize) -> i128 {
+        let mut sum = 0;
+        for block in self.blocks.iter().rev() {
+            let (count, total) = block.tallies[side.index()];
+            if count <= n {
+                n -= count;
+                sum += total;
+                continue;
+            }
+            for start in block.starts.iter().rev() {
+                let counts = self.counts[start];
+                let copies = n.min(if side == Side::Left {
+                    counts.0
+                } else {
+                    counts.1
+                });
+                sum += copies as i128 * start;
+                n -= copies;
+                if n == 0 {
+                    break;
+                }
+            }
+            break;
+        }
+        sum
+    }
+
+    fn insert(&mut self, id: i128, side: Side) {
+        // The segment goes in first, as splitting a block looks up the
+        // counts of every ID in it
+        if let Entry::Vacant(entry) = self.counts.entry(id) {
+            entry.insert((0, 0));
+            self.add_segment(id);
+        }
+        let counts = self.counts.get_mut(&id).unwrap();
+        let (mine, theirs, delta) = match side {
+            Side::Left => (&mut counts.0, counts.1, 1),
+            Side::Right => (&mut counts.1, counts.0, -1),
+        };
+        *mine += 1;
+        self.similarity += id * theirs as i128;
+        match side {
+            Side::Left => self.left_len += 1,
+            Side::Right => self.right_len += 1,
+        }
+
+        self.tally(id, side, 1);
+        self.shift_from(id, delta);
+    }
+
+    fn remove(&mut self, id: i128, side: Side) -> bool {
+        let Some(counts) = self.counts.get_mut(&id) else {
+            return false;
+        };
+        let (mine, theirs, delta) = match side {
+            Side::Left => (&mut counts.0, counts.1, -1),
+            Side::Right => (&mut counts.1, counts.0, 1),
+        };
+        if *mine == 0 {
+            return false;
+        }
+        *mine -= 1;
+        self.similarity -= id * theirs as i128;
+        match side {
+            Side::Left => self.left_len -= 1,
+            Side::Right => self.right_len -= 1,
+        }
+
+        self.tally(id, side, -1);
+        self.shift_from(id, delta);
+        if self.counts[&id] == (0, 0) {
+            self.counts.remove(&id);
+            self.remove_segment(id);
+        }
+        true
+    }
+
+    // Adds copies of an ID to the tally of the block its segment is in
+    fn tally(&mut self, id: i128, side: Side, copies: i128) {
+        if let Some((b, _)) = self.find(id) {
+            self.blocks[b].tally(side, id, copies);
+        }
+    }
+
+    // The block and index of the segment that covers an ID, or None if it
+    // comes before every segment
+    fn find(&self, id: i128) -> Option<(usize, usize)> {
+        let b = self
+            .blocks
+            .partition_point(|block| block.starts[0] <= id)
+            .checked_sub(1)?;
+        let i = self.blocks[b].starts.partition_point(|&start| start <= id) - 1;
+        Some((b, i))
+    }
+
+    // The segment before the one at (b, i), if there is one
+    fn previous(&self, b: usize, i: usize) -> Option<(usize, usize)> {
+        match i.checked_sub(1) {
+            Some(i) => Some((b, i)),
+            None => b.checked_sub(1).map(|b| (b, self.blocks[b].len() - 1)),
+        }
+    }
+
+    fn is_last(&self, b: usize, i: usize) -> bool {
+        b + 1 == self.blocks.len() && i + 1 == self.blocks[b].len()
+    }
+
+    // Cuts the segment covering a new ID in two at that ID. Both halves have
+    // the same difference, so only a new last segment moves the distance,
+    // by stretching the old last one up to it
+    fn add_segment(&mut self, id: i128) {
+        match self.find(id) {
+            None => {
+                // Nothing comes before the first ID, so the difference is zero
+                if self.blocks.is_empty() {
+                    self.blocks.push(Block::default());
+                }
+                let block = &mut self.blocks[0];
+                let width = block.starts.first().map_or(0, |&first| first.abs_diff(id));
+                let diff = -block.add;
+                block.starts.insert(0, id);
+                block.widths.insert(0, width);
+                block.diffs.insert(0, diff);
+                block.count(width, diff);
+                self.after_insert(0);
+            }
+            Some((b, i)) => {
+                let is_last = self.is_last(b, i);
+                let block = &mut self.blocks[b];
+                let (start, width, diff) = (block.starts[i], block.widths[i], block.diffs[i]);
+                let before = start.abs_diff(id);
+                let after = if is_last { 0 } else { width - before };
+                self.distance += block.update(i, before, diff);
+                block.starts.insert(i + 1, id);
+                block.widths.insert(i + 1, after);
+                block.diffs.insert(i + 1, diff);
+                block.count(after, diff);
+                self.distance += block.cost(i + 1);
+                self.after_insert(b);
+            }
+        }
+    }
+
+    // Joins the segment at an ID no longer in either list onto the one
+    // before it. Its difference is now the same as that one's, unless it was
+    // the first segment, where it is zero
+    fn remove_segment(&mut self, id: i128) {
+        let Some((b, i)) = self.find(id) else {
+            return;
+        };
+        let is_last = self.is_last(b, i);
+        let previous = self.previous(b, i);
+
+        let block = &mut self.blocks[b];
+        let width = block.widths[i];
+        self.distance -= block.cost(i);
+        block.uncount(width, block.diffs[i]);
+        block.starts.remove(i);
+        block.widths.remove(i);
+        block.diffs.remove(i);
+
+        if let Some((pb, pi)) = previous {
+            let block = &mut self.blocks[pb];
+            let joined = if is_last { 0 } else { block.widths[pi] + width };
+            let diff = block.diffs[pi];
+            self.distance += block.update(pi, joined, diff);
+        }
+
+        if self.blocks[b].len() == 0 {
+            self.blocks.remove(b);
+        }
+        self.segments -= 1;
+        if self.blocks.len() > 2 * self.segments / self.block_size() + 2 {
+            self.rebuild();
+        }
+    }
+
+    // Adds one to, or takes one from, the difference of every segment from
+    // the ID onwards
+    fn shift_from(&mut self, id: i128, delta: i64) {
+        let Some((b, i)) = self.find(id) else {
+            return;
+        };
+        let block = &mut self.blocks[b];
+        for j in i..block.len() {
+            let (width, diff) = (block.widths[j], block.diffs[j]);
+            self.distance += block.update(j, width, diff + delta);
+        }
+        for block in &mut self.blocks[b + 1..] {
+            self.distance += block.shift(delta);
+        }
+    }
+
+    fn block_size(&self) -> usize {
+        MIN_BLOCK.max((self.segments as f64).sqrt() as usize)
+    }
+
+    // Splits a block that has grown too big in two
+    fn after_insert(&mut self, b: usize) {
+        self.segments += 1;
+        if self.blocks[b].len() > 2 * self.block_size() {
+            let segments: Vec<_> = self.blocks[b].segments().collect();
+            let (first, second) = segments.split_at(segments.len() / 2);
+            self.blocks[b] = Block::new(first, &self.counts);
+            self.blocks.insert(b + 1, Block::new(second, &self.counts));
+        }
+    }
+
+    // Evens out the blocks after removals have left many small ones
+    fn rebuild(&mut self) {
+        let segments: Vec<_> = self.blocks.iter().flat_map(Block::segments).collect();
+        let size = self.block_size();
+        self.blocks = segments
+            .chunks(size)
+            .map(|segments| Block::new(segments, &self.counts))
+            .collect();
+    }
+}
+
+impl<T: NarrowId> FromIterator<(T, T)> for IncrementalListPair<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(pairs: I) -> Self {
+        let mut lists = IncrementalListPair::new();
+        for (left, right) in pairs {
+            lists.insert_left(left);
+            lists.insert_right(right);
+        }
+        lists
+    }
+}
+
+impl<T: NarrowId> From<&ListPair<T>> for IncrementalListPair<T> {
+    fn from(lists: &ListPair<T>) -> Self {
+        let mut incremental = IncrementalListPair::new();
+        for &id in lists.left() {
+            incremental.insert_left(id);
+        }
+        for &id in lists.right() {
+            incremental.insert_right(id);
+        }
+        incremental
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Rng;
+
+    #[test]
+    fn test_example() {
+        let lists: ListPair = ListPair::from_iter([(3, 4), (4, 3), (2, 5), (1, 3), (3, 9), (3, 3)]);
+        let incremental = IncrementalListPair::from(&lists);
+        assert_eq!(incremental.total_distance(), Ok(11));
+        assert_eq!(incremental.similarity_score(), Ok(31));
+    }
+
+    #[test]
+    fn test_unequal_lengths() {
+        let lists: ListPair = ListPair::new(vec![1, 2, 3], vec![5]);
+        let incremental = IncrementalListPair::from(&lists);
+        assert_eq!(incremental.total_distance(), lists.total_distance());
+        assert_eq!(incremental.total_distance(), Ok(4));
+
+        let mut lists = IncrementalListPair::<i64>::new();
+        lists.insert_left(1);
+        lists.insert_left(5);
+        lists.insert_right(2);
+        // 1 pairs with 2, and 5 is left out
+        assert_eq!(lists.total_distance(), Ok(1));
+        assert!(!lists.remove_right(7));
+        lists.insert_right(-3);
+        // -3 pairs with 1, and 2 with 5
+        assert_eq!(lists.total_distance(), Ok(7));
+        assert!(lists.remove_right(-3));
+        assert!(lists.remove_left(5));
+        assert_eq!(lists.total_distance(), Ok(1));
+        assert!(lists.remove_left(1));
+        assert!(lists.remove_right(2));
+        assert_eq!(lists.total_distance(), Ok(0));
+        assert_eq!((lists.left_len(), lists.right_len()), (0, 0));
+    }
+
+    #[test]
+    fn test_overflow_is_an_error() {
+        let lists = IncrementalListPair::from_iter([(i32::MAX, i32::MAX), (i32::MAX, i32::MIN)]);
+        assert_eq!(
+            lists.similarity_score(),
+            Err(Error::overflow("the similarity score", "i32"))
+        );
+        assert_eq!(lists.total_distance(), Ok(u32::MAX));
+        let lists = IncrementalListPair::from_iter([(i32::MIN, i32::MAX), (i32::MIN, i32::MAX)]);
+        assert_eq!(
+            lists.total_distance(),
+            Err(Error::overflow("the total distance", "u32"))
+        );
+    }
+
+    // Random insertions and removals, checked against ListPair sorting from
+    // scratch after every one. Wide enough to split and rebuild the blocks
+    #[test]
+    fn test_agrees_with_list_pair() {
+        for seed in 0..4 {
+            let mut rng = Rng::new(seed);
+            let spread = [20, 400, 100_000, 2_000_000_000][seed as usize];
+            let mut lists = IncrementalListPair::new();
+            let (mut left, mut right): (Vec<i64>, Vec<i64>) = (Vec::new(), Vec::new());
+
+            for step in 0..3000 {
+                let is_left = rng.chance(0.5);
+                let side = if is_left { &mut left } else { &mut right };
+                // Grow for a while, then shrink back down
+                let remove = !side.is_empty() && rng.chance(if step < 2000 { 0.3 } else { 0.8 });
+                if remove {
+                    let id = side.swap_remove(rng.index(side.len()));
+                    assert!(if is_left {
+                        lists.remove_left(id)
+                    } else {
+                        lists.remove_right(id)
+                    });
+                } else {
+                    let id = rng.range(-spread..spread);
+                    side.push(id);
+                    if is_left {
+                        lists.insert_left(id);
+                    } else {
+                        lists.insert_right(id);
+                    }
+                }
+
+                let expected = ListPair::new(left.clone(), right.clone());
+                assert_eq!(
+                    lists.total_distance(),
+                    expected.total_distance(),
+                    "seed {} step {}",
+                    seed,
+                    step
+                );
+                assert_eq!(lists.similarity_score(), expected.similarity_score());
+            }
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use crate::id::Id;
+use aoc_common::error::{self, Error};
+use std::fmt;
+use std::str::FromStr;
+
+// What separates the columns on each line
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Delimiter {
+    // Any run of spaces and tabs, as in the puzzle input
+    Whitespace,
+    // A single character, e.g. ',' for CSV or '\t' for TSV. Fields may be
+    // wrapped in double quotes, and the delimiter inside quotes is ignored
+    Char(char),
+}
+
+// A column picked out of a wider table, by its position counting from 0 or
+// by the name in its header
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+// How the two lists are laid out in the input. Anything left as None is
+// worked out from the first line
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Layout {
+    pub delimiter: Option<Delimiter>,
+    pub header: Option<bool>,
+    pub columns: [Column; 2],
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout {
+            delimiter: None,
+            header: None,
+            columns: [Column::Index(0), Column::Index(1)],
+        }
+    }
+}
+
+impl Delimiter {
+    // Tabs, then commas, then semicolons, whichever the line has first in
+    // that order, and whitespace if it has none of them
+    pub fn detect(line: &str) -> Delimiter {
+        ['\t', ',', ';']
+            .into_iter()
+            .find(|&c| line.contains(c))
+            .map_or(Delimiter::Whitespace, Delimiter::Char)
+    }
+
+    // The fields of `line`, trimmed and unquoted, as slices of it so errors
+    // can point at them
+    pub fn split<'a>(&self, line: &'a str) -> Vec<&'a str> {
+        let delimiter = match *self {
+            Delimiter::Whitespace => return line.split_whitespace().collect(),
+            Delimiter::Char(c) => c,
+        };
+        let mut fields = Vec::new();
+        let mut start = 0;
+        let mut quoted = false;
+        for (i, c) in line.char_indices() {
+            if c == '"' {
+                quoted = !quoted;
+            } else if c == delimiter && !quoted {
+                fields.push(unquote(&line[start..i]));
+                start = i + c.len_utf8();
+            }
+        }
+        fields.push(unquote(&line[start..]));
+        fields
+    }
+}
+
+fn unquote(field: &str) -> &str {
+    let field = field.trim();
+    field
+        .strip_prefix('"')
+        .and_then(|field| field.strip_suffix('"'))
+        .unwrap_or(field)
+}
+
+// "whitespace", "tab", "comma" or "semicolon", or the character itself
+impl FromStr for Delimiter {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "whitespace" | "space" | " " => Ok(Delimiter::Whitespace),
+            "tab" | "\\t" => Ok(Delimiter::Char('\t')),
+            "comma" => Ok(Delimiter::Char(',')),
+            "semicolon" => Ok(Delimiter::Char(';')),
+            _ => {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(Delimiter::Char(c)),
+                    _ => Err(format!("unknown delimiter {:?}", text)),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Column::Index(index) => write!(f, "column {}", index + 1),
+            Column::Name(name) => write!(f, "column {:?}", name),
+        }
+    }
+}
+
+// A column number, counting from 1 as spreadsheets do, or a header name
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        match text.parse::<usize>() {
+            Ok(0) => Err("columns are numbered from 1".to_string()),
+            Ok(number) => Ok(Column::Index(number - 1)),
+            Err(_) if text.is_empty() => Err("a column needs a number or name".to_string()),
+            Err(_) => Ok(Column::Name(text.to_string())),
+        }
+    }
+}
+
+impl Layout {
+    // Builds a layout from a day's parameters: a delimiter, whether there is
+    // a header ("true" or "false") and the two columns separated by a comma,
+    // e.g. "2,5" or "left,right"
+    pub fn from_params(
+        delimiter: Option<&str>,
+        header: Option<&str>,
+        columns: Option<&str>,
+    ) -> Result<Layout, String> {
+        let mut layout = Layout::default();
+        if let Some(delimiter) = delimiter {
+            layout.delimiter = Some(delimiter.parse()?);
+        }
+        if let Some(header) = header {
+            layout.header = Some(
+                header
+                    .parse()
+                    .map_err(|_| format!("header should be true or false, not {:?}", header))?,
+            );
+        }
+        if let Some(columns) = columns {
+            let columns: Vec<&str> = columns.split(',').collect();
+            layout.columns = match columns[..] {
+                [left, right] => [left.parse()?, right.parse()?],
+                _ => return Err("columns should be two columns separated by a comma".to_string()),
+            };
+        }
+        Ok(layout)
+    }
+
+    // Reads the two columns out of `input`. Blank lines are skipped, and a
+    // byte order mark left by a spreadsheet is ignored
+    pub fn read<T: Id>(&self, input: &str) -> error::Result<Vec<(T, T)>> {
+        let mut pairs = Vec::new();
+        self.read_lines(input.lines().map(Ok), |left, right| {
+            pairs.push((left, right));
+            Ok(())
+        })?;
+        Ok(pairs)
+    }
+
+    // Reads the two columns a line at a time, handing each pair of IDs to
+    // `pair`, for inputs too big to hold in memory at once
+    pub fn read_lines<T: Id, L: AsRef<str>>(
+        &self,
+        lines: impl IntoIterator<Item = error::Result<L>>,
+        mut pair: impl FnMut(T, T) -> error::Result<()>,
+    ) -> error::Result<()> {
+        let mut columns = None;
+        for (i, line) in lines.into_iter().enumerate() {
+            let line = line?;
+            let number = i + 1;
+            let line = match number {
+                1 => line.as_ref().trim_start_matches('\u{feff}'),
+                _ => line.as_ref(),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (delimiter, left, right) = match columns {
+                Some(columns) => columns,
+                None => {
+                    let (found, header) = self.columns(number, line)?;
+                    columns = Some(found);
+                    if header {
+                        continue;
+                    }
+                    found
+                }
+            };
+
+            let fields = delimiter.split(line);
+            let field = |index: usize| {
+                let field = fields.get(index).copied().ok_or_else(|| {
+                    Error::expected(
+                        number,
+                        line.len() + 1,
+                        &format!("a value in {}", Column::Index(index)),
+                    )
+                })?;
+                error::parse_number::<T>(field, number, error::column(line, field))
+            };
+            pair(field(left)?, field(right)?)?;
+        }
+        Ok(())
+    }
+
+    // Works out from the first line what separates the columns and where
+    // the two wanted ones are, and whether the line is a header to skip
+    fn columns(
+        &self,
+        number: usize,
+        first: &str,
+    ) -> error::Result<((Delimiter, usize, usize), bool)> {
+        let delimiter = self.delimiter.unwrap_or_else(|| Delimiter::detect(first));
+        let header = self
+            .header
+            .unwrap_or_else(|| self.looks_like_header(&delimiter, first));
+        let columns = if header {
+            let names = delimiter.split(first);
+            [0, 1].map(|i| find(&self.columns[i], &names))
+        } else {
+            [0, 1].map(|i| match &self.columns[i] {
+                Column::Index(index) => Some(*index),
+                Column::Name(_) => None,
+            })
+        };
+        let [Some(left), Some(right)] = columns else {
+            let missing = &self.columns[columns.iter().position(Option::is_none).unwrap_or(0)];
+            let expected = if header {
+                format!("a header naming {}", missing)
+            } else {
+                format!("a header row to find {} in", missing)
+            };
+            return Err(Error::expected(number, 1, &expected));
+        };
+        Ok(((delimiter, left, right), header))
+    }
+
+    // The first line must be a header if a column is picked by name.
+    // Otherwise it's only taken for one if none of the columns to be read
+    // from it look like numbers, so that a mistyped or overflowing first ID
+    // is still reported rather than skipped
+    fn looks_like_header(&self, delimiter: &Delimiter, line: &str) -> bool {
+        let fields = delimiter.split(line);
+        self.columns
+            .iter()
+            .any(|column| matches!(column, Column::Name(_)))
+            || self.columns.iter().all(|column| match column {
+                Column::Index(index) => fields.get(*index).is_some_and(|field| !is_numeric(field)),
+                Column::Name(_) => true,
+            })
+    }
+}
+
+// An optional sign and then only digits, whether or not it fits in an ID
+fn is_numeric(field: &str) -> bool {
+    let digits = field.strip_prefix(['-', '+']).unwrap_or(field);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+// Where a column is in a header row
+fn find(column: &Column, names: &[&str]) -> Option<usize> {
+    match column {
+        Column::Index(index) => Some(*index),
+        Column::Name(name) => names.iter().position(|field| field == name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(layout: &Layout, input: &str) -> error::Result<Vec<(i64, i64)>> {
+        layout.read(input)
+    }
+
+    #[test]
+    fn test_detects_delimiters() {
+        let expected = Ok(vec![(3, 4), (4, 3)]);
+        let layout = Layout::default();
+        assert_eq!(read(&layout, "3   4\n4   3\n"), expected);
+        assert_eq!(read(&layout, "3 4\n4 \t 3\n"), expected);
+        assert_eq!(read(&layout, "3\t4\n4\t3\n"), expected);
+        assert_eq!(read(&layout, "3,4\r\n4, 3\r\n\r\n"), expected);
+        assert_eq!(read(&layout, "\u{feff}\"3\";\"4\"\n4;3\n"), expected);
+    }
+
+    #[test]
+    fn test_detects_header() {
+        let layout = Layout::default();
+        assert_eq!(read(&layout, "left,right\n3,4\n"), Ok(vec![(3, 4)]));
+        assert_eq!(read(&layout, "3,4\n"), Ok(vec![(3, 4)]));
+        let layout = Layout {
+            header: Some(false),
+            ..Layout::default()
+        };
+        assert_eq!(
+            read(&layout, "left,right\n3,4\n"),
+            Err(Error::InvalidNumber {
+                location: error::Location::new(1, 1),
+                text: "left".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_bad_first_line_is_not_a_header() {
+        let layout = Layout::default();
+        assert_eq!(
+            read(&layout, "x   4\n1   2\n"),
+            Err(Error::InvalidNumber {
+                location: error::Location::new(1, 1),
+                text: "x".to_string(),
+            })
+        );
+        assert_eq!(
+            read(&layout, "99999999999999999999   4\n1   2\n"),
+            Err(Error::InvalidNumber {
+                location: error::Location::new(1, 1),
+                text: "99999999999999999999".to_string(),
+            })
+        );
+        assert_eq!(
+            read(&layout, "3,-x\n"),
+            Err(Error::InvalidNumber {
+                location: error::Location::new(1, 3),
+                text: "-x".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_picks_columns() {
+        let table = "Name,\"Left, ID\",Notes,Right ID\nA,3,\"x, y\",4\nB,4,,3\n";
+        let by_name = Layout {
+            columns: [
+                Column::Name("Right ID".to_string()),
+                Column::Name("Left, ID".to_string()),
+            ],
+            ..Layout::default()
+        };
+        assert_eq!(read(&by_name, table), Ok(vec![(4, 3), (3, 4)]));
+        let by_index = Layout::from_params(Some(","), Some("true"), Some("2,4")).unwrap();
+        assert_eq!(read(&by_index, table), Ok(vec![(3, 4), (4, 3)]));
+    }
+
+    #[test]
+    fn test_errors() {
+        let layout = Layout::default();
+        assert_eq!(
+            read(&layout, "3\n"),
+            Err(Error::expected(1, 2, "a value in column 2"))
+        );
+        let layout = Layout::from_params(None, None, Some("left,right")).unwrap();
+        assert_eq!(
+            read(&layout, "\nleft,other\n3,4\n"),
+            Err(Error::expected(2, 1, "a header naming column \"right\""))
+        );
+        let layout = Layout::from_params(None, Some("false"), Some("left,2")).unwrap();
+        assert_eq!(
+            read(&layout, "3,4\n"),
+            Err(Error::expected(
+                1,
+                1,
+                "a header row to find column \"left\" in"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_from_params() {
+        assert_eq!(
+            Layout::from_params(Some("tab"), None, Some("1, 3")),
+            Ok(Layout {
+                delimiter: Some(Delimiter::Char('\t')),
+                header: None,
+                columns: [Column::Index(0), Column::Index(2)],
+            })
+        );
+        assert!(Layout::from_params(Some("::"), None, None).is_err());
+        assert!(Layout::from_params(None, Some("yes"), None).is_err());
+        assert!(Layout::from_params(None, None, Some("0,1")).is_err());
+        assert!(Layout::from_params(None, None, Some("1")).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+mod external;
+mod generate;
+mod id;
+mod incremental;
+mod layout;
+mod list_pair;
+
+pub use external::{ExternalSort, SortedLists};
+pub use id::{Id, NarrowId, Number};
+pub use incremental::IncrementalListPair;
+pub use layout::{Column, Delimiter, Layout};
+pub use list_pair::{ListPair, PairDistance, Similarity};
+
+use aoc_common::error;
+use aoc_common::{Answer, Solution};
+
+pub struct Day1;
+
+impl Day1 {
+    // Parses the lists from a table laid out as `layout` says
+    pub fn parse_with(input: &str, layout: &Layout) -> error::Result<ListPair> {
+        ListPair::parse_with(input, layout)
+    }
+}
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    // The left and right columns of location IDs
+    type Input = ListPair;
+
+    fn parse(input: &str) -> error::Result<Self::Input> {
+        Day1::parse_with(input, &Layout::default())
+    }
+
+    // Pair up the sorted lists and sum the differences, or fail if the total
+    // doesn't fit in a u64
+    fn part1(lists: &Self::Input) -> error::Result<Answer> {
+        Ok(lists.total_distance()?.into())
+    }
+
+    // Weight each left number by how often it appears on the right, or fail
+    // if the total doesn't fit in an i64
+    fn part2(lists: &Self::Input) -> error::Result<Answer> {
+        Ok(lists.similarity_score()?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_past_i32() {
+        assert_eq!(
+            Day1::solve_str("3000000000   3000000001\n"),
+            Ok([Answer::Int(1), Answer::Int(0)])
+        );
+    }
+
+    #[test]
+    fn test_overflow_is_reported() {
+        let max = i64::MAX;
+        let input = format!("{}   {}\n{}   1\n", max, max, max);
+        assert_eq!(
+            Day1::solve_str(&input),
+            Err(error::Error::overflow("the similarity score", "i64"))
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use crate::id::{self, Id};
+use crate::layout::Layout;
+use aoc_common::error::{self, Error};
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+// The two lists of location IDs, left and right, in the order they were given.
+// The IDs can be any width of integer; the totals are added up in the same
+// width and are an error rather than a wrong answer if they don't fit
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListPair<T: Id = i64> {
+    left: Vec<T>,
+    right: Vec<T>,
+}
+
+// The nth smallest IDs of each list paired up, and how far apart they are
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PairDistance<T: Id = i64> {
+    pub left: T,
+    pub right: T,
+    pub distance: T::Distance,
+}
+
+// An ID from the left list, how often it appears in the right list and what
+// that adds to the similarity score
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Similarity<T: Id = i64> {
+    pub id: T,
+    pub count: usize,
+    pub score: T,
+}
+
+impl<T: Id> ListPair<T> {
+    // The lists should be the same length; any extra IDs in the longer one
+    // have nothing to pair with and are left out of the distance
+    pub fn new(left: Vec<T>, right: Vec<T>) -> Self {
+        ListPair { left, right }
+    }
+
+    pub fn left(&self) -> &[T] {
+        &self.left
+    }
+
+    pub fn right(&self) -> &[T] {
+        &self.right
+    }
+
+    pub fn len(&self) -> usize {
+        self.left.len().min(self.right.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Pair up the sorted lists, smallest with smallest
+    pub fn distances(&self) -> Vec<PairDistance<T>> {
+        self.left
+            .iter()
+            .sorted()
+            .zip(self.right.iter().sorted())
+            .map(|(&left, &right)| PairDistance {
+                left,
+                right,
+                distance: left.distance(right),
+            })
+            .collect()
+    }
+
+    pub fn total_distance(&self) -> error::Result<T::Distance> {
+        id::checked_sum(
+            self.distances().iter().map(|pair| pair.distance),
+            "the total distance",
+        )
+    }
+
+    // How many times each ID appears in the right list
+    pub fn right_counts(&self) -> HashMap<T, usize> {
+        self.right.iter().fold(HashMap::new(), |mut acc, num| {
+            *acc.entry(*num).or_insert(0) += 1;
+            acc
+        })
+    }
+
+    // Weight each left ID by how often it appears on the right, in the left
+    // list's order. An ID too big to weight by its count is an error
+    pub fn similarities(&self) -> error::Result<Vec<Similarity<T>>> {
+        let right_counts = self.right_counts();
+        self.left
+            .iter()
+            .map(|&id| {
+                let count = right_counts.get(&id).copied().unwrap_or(0);
+                let score = id
+                    .times(count)
+                    .ok_or_else(|| Error::overflow(&format!("the score for {}", id), T::NAME))?;
+                Ok(Similarity { id, count, score })
+            })
+            .collect()
+    }
+
+    pub fn similarity_score(&self) -> error::Result<T> {
+        id::checked_sum(
+            self.similarities()?
+                .iter()
+                .map(|similarity| similarity.score),
+            "the similarity score",
+        )
+    }
+}
+
+impl<T: Id> FromIterator<(T, T)> for ListPair<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(pairs: I) -> Self {
+        let (left, right) = pairs.into_iter().unzip();
+        ListPair { left, right }
+    }
+}
+
+impl<T: Id> ListPair<T> {
+    // Reads the lists out of a table laid out as `layout` says
+    pub fn parse_with(input: &str, layout: &Layout) -> error::Result<Self> {
+        Ok(layout.read(input)?.into_iter().collect())
+    }
+}
+
+// One pair of IDs per line, as the puzzle gives them or in the first two
+// columns of a CSV or TSV file, with or without a header
+impl<T: Id> FromStr for ListPair<T> {
+    type Err = Error;
+
+    fn from_str(input: &str) -> error::Result<Self> {
+        ListPair::parse_with(input, &Layout::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> ListPair {
+        ListPair::from_iter([(3, 4), (4, 3), (2, 5), (1, 3), (3, 9), (3, 3)])
+    }
+
+    #[test]
+    fn test_parse() {
+        let lists: ListPair = "3   4\n4   3\n".parse().unwrap();
+        assert_eq!(lists, ListPair::new(vec![3, 4], vec![4, 3]));
+        assert_eq!(
+            "3\n".parse::<ListPair>(),
+            Err(Error::expected(1, 2, "a value in column 2"))
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let lists = example();
+        assert_eq!(
+            lists.distances()[..2],
+            [
+                PairDistance {
+                    left: 1,
+                    right: 3,
+                    distance: 2,
+                },
+                PairDistance {
+                    left: 2,
+                    right: 3,
+                    distance: 1,
+                },
+            ]
+        );
+        assert_eq!(lists.total_distance(), Ok(11));
+    }
+
+    #[test]
+    fn test_similarities() {
+        let lists = example();
+        assert_eq!(
+            lists.similarities().unwrap()[0],
+            Similarity {
+                id: 3,
+                count: 3,
+                score: 9,
+            }
+        );
+        assert_eq!(lists.right_counts()[&3], 3);
+        assert_eq!(lists.similarity_score(), Ok(31));
+    }
+
+    #[test]
+    fn test_uneven_lists() {
+        let lists: ListPair = ListPair::new(vec![1, 2, 3], vec![5]);
+        assert_eq!(lists.len(), 1);
+        assert_eq!(lists.total_distance(), Ok(4));
+    }
+
+    #[test]
+    fn test_overflow_is_an_error() {
+        let lists = ListPair::new(vec![i64::MIN, i64::MIN], vec![i64::MAX, i64::MAX]);
+        assert_eq!(
+            lists.total_distance(),
+            Err(Error::overflow("the total distance", "u64"))
+        );
+        let lists = ListPair::new(vec![i64::MAX, 1], vec![i64::MAX, i64::MAX]);
+        assert_eq!(
+            lists.similarity_score(),
+            Err(Error::overflow(
+                &format!("the score for {}", i64::MAX),
+                "i64"
+            ))
+        );
+        let lists = ListPair::new(vec![i64::MAX, i64::MAX], vec![i64::MAX]);
+        assert_eq!(
+            lists.similarity_score(),
+            Err(Error::overflow("the similarity score", "i64"))
+        );
+    }
+
+    #[test]
+    fn test_wider_ids() {
+        let big = u64::MAX as u128 + 1;
+        let lists: ListPair<u128> = format!("{}   {}\n{}   {}\n", big, big, big, 0)
+            .parse()
+            .unwrap();
+        assert_eq!(lists.total_distance(), Ok(big));
+        assert_eq!(lists.similarity_score(), Ok(2 * big));
+    }
+}
@@ -1,60 +1,79 @@
-use itertools::Itertools;
-use std::collections::HashMap;
-use std::env;
-use std::fs::File;
-use std::io::{self, prelude::*, BufReader};
+use aoc_common::{error, input, output, Answer, Args, Part, Solution};
+use day1::{Day1, ExternalSort, Layout};
+use std::num::NonZeroUsize;
+use std::time::Instant;
 
-fn main() -> io::Result<()> {
-    let args: Vec<String> = env::args().collect();
+fn main() {
+    let args = Args::from_env::<Day1>(
+        &["delimiter", "header", "columns", "run_size", "spill_dir"],
+        &[
+            "<filename> [--delimiter <d>] [--header true|false] [--columns <a>,<b>]    (columns by number from 1 or header name)",
+            "<filename> --run-size <n> [--spill-dir <dir>]    (sort n IDs at a time on disk, for inputs too big for memory)",
+        ],
+    );
+    let layout = Layout::from_params(
+        args.param("delimiter"),
+        args.param("header"),
+        args.param("columns"),
+    )
+    .unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
 
-    if args.len() != 2 {
-        eprintln!("Usage: {} <filename>", args[0]);
+    if args.param("spill_dir").is_some() && args.param("run_size").is_none() {
+        eprintln!("error: --spill-dir only applies along with --run-size");
         std::process::exit(1);
     }
 
-    let filename = &args[1];
-    let file = File::open(filename)?;
-    let reader = BufReader::new(file);
-
-    let (left_numbers, right_numbers): (Vec<i32>, Vec<i32>) = reader
-        .lines()
-        .map(|line: Result<String, io::Error>| {
-            let line = line.unwrap();
-            let (left_str, right_str) = line
-                .split_once("   ")
-                .expect("Expected two numbers per line");
-            (
-                left_str.parse::<i32>().unwrap(),
-                right_str.parse::<i32>().unwrap(),
-            )
-        })
-        .unzip();
-
-    // Part one
-    println!(
-        "Total sum of differences: {}",
-        left_numbers
-            .iter()
-            .sorted()
-            .zip(right_numbers.iter().sorted())
-            .map(|(left, right)| left.abs_diff(*right))
-            .sum::<u32>()
-    );
-
-    // Part two
-
-    let right_counts: HashMap<i32, i32> =
-        right_numbers.iter().fold(HashMap::new(), |mut acc, num| {
-            *acc.entry(*num).or_insert(0) += 1;
-            acc
+    if let Some(run_size) = args.param("run_size") {
+        let run_len = run_size.parse().unwrap_or_else(|_| {
+            eprintln!("error: run size must be a whole number above 0");
+            std::process::exit(1);
         });
+        solve_external(&args, &layout, run_len).unwrap_or_else(|e| e.exit());
+        return;
+    }
+
+    let input = input::load(&args.filename)
+        .and_then(|text| Day1::parse_with(&text, &layout))
+        .map_err(|e| e.in_file(input::source_name(&args.filename)))
+        .unwrap_or_else(|e| e.exit());
 
-    let part_two_sum: i32 = left_numbers
-        .iter()
-        .map(|left| *left * right_counts.get(left).unwrap_or(&0))
-        .sum();
+    output::print_part::<Day1>(args.format, &input, Part::One, "Total sum of differences")
+        .unwrap_or_else(|e| e.exit());
+    output::print_part::<Day1>(args.format, &input, Part::Two, "Part two sum")
+        .unwrap_or_else(|e| e.exit());
+}
 
-    println!("Part two sum: {}", part_two_sum);
+// Solves both parts without loading the input into memory, by sorting it
+// into runs on disk. The runs are deleted before any error is returned
+fn solve_external(args: &Args, layout: &Layout, run_len: NonZeroUsize) -> error::Result<()> {
+    let mut sort = ExternalSort::<i64>::new(run_len);
+    if let Some(dir) = args.param("spill_dir") {
+        sort = sort.in_dir(dir);
+    }
+    let lists = input::open_lines(&args.filename)
+        .and_then(|lines| sort.read(lines, layout))
+        .map_err(|e| e.in_file(input::source_name(&args.filename)))?;
 
+    for (part, label) in [
+        (Part::One, "Total sum of differences"),
+        (Part::Two, "Part two sum"),
+    ] {
+        let start = Instant::now();
+        let answer: Answer = match part {
+            Part::One => lists.total_distance()?.into(),
+            Part::Two => lists.similarity_score()?.into(),
+        };
+        output::print_result(
+            args.format,
+            label,
+            Day1::DAY,
+            part,
+            &answer,
+            start.elapsed(),
+        );
+    }
     Ok(())
 }
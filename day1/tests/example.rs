@@ -0,0 +1,80 @@
+// Runs day 1 against the example in test.txt, the way the puzzle describes it
+use aoc_common::{Answer, Solution};
+use day1::Day1;
+use std::process::Command;
+
+const EXAMPLE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/test.txt");
+
+fn run_main(args: &[&str]) -> String {
+    // An empty AOC_CONFIG ignores any .aoc.toml around the checkout
+    let output = Command::new(env!("CARGO_BIN_EXE_day1"))
+        .args(args)
+        .env("AOC_CONFIG", "")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_example_answers() {
+    let input = include_str!("../test.txt");
+    assert_eq!(
+        Day1::solve_str(input),
+        Ok([Answer::Int(11), Answer::Int(31)])
+    );
+}
+
+#[test]
+fn test_main_prints_example_answers() {
+    assert_eq!(
+        run_main(&[EXAMPLE]),
+        "Total sum of differences: 11\nPart two sum: 31\n"
+    );
+}
+
+#[test]
+fn test_main_reads_spreadsheet_export() {
+    let table = std::env::temp_dir().join(format!("aoc-day1-table-{}.csv", std::process::id()));
+    let rows: String = include_str!("../test.txt")
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let ids: Vec<&str> = line.split_whitespace().collect();
+            format!("{};row {};{}\n", ids[1], i + 1, ids[0])
+        })
+        .collect();
+    std::fs::write(&table, format!("Right;Note;Left\n{}", rows)).unwrap();
+    let table = table.to_str().unwrap();
+
+    let expected = "Total sum of differences: 11\nPart two sum: 31\n";
+    assert_eq!(run_main(&[table, "--columns", "Left,Right"]), expected);
+    assert_eq!(
+        run_main(&["--delimiter", ";", table, "--columns", "3,1"]),
+        expected
+    );
+    std::fs::remove_file(table).unwrap();
+}
+
+#[test]
+fn test_main_sorts_on_disk() {
+    let dir = std::env::temp_dir().join(format!("aoc-day1-spill-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let dir_name = dir.to_str().unwrap();
+
+    assert_eq!(
+        run_main(&[EXAMPLE, "--run-size", "2", "--spill-dir", dir_name]),
+        "Total sum of differences: 11\nPart two sum: 31\n"
+    );
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+
+    // A spill directory means nothing without a run size
+    let output = Command::new(env!("CARGO_BIN_EXE_day1"))
+        .args([EXAMPLE, "--spill-dir", dir_name])
+        .env("AOC_CONFIG", "")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    std::fs::remove_dir(&dir).unwrap();
+}
@@ -0,0 +1,61 @@
+use crate::Day2;
+use aoc_common::{Generate, Rng};
+
+impl Generate for Day2 {
+    // `size` reports of five to eight levels. Most step steadily up or down by
+    // one to three, but some have a bad step (flat, too big or the wrong way)
+    // and a few have two, so every kind of report turns up
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let len = rng.range(5..9) as usize;
+                let direction = if rng.chance(0.5) { 1 } else { -1 };
+                let bad_steps = match rng.range(0..10) {
+                    0..=4 => 0,
+                    5..=8 => 1,
+                    _ => 2,
+                };
+                let bad: Vec<usize> = (0..bad_steps).map(|_| rng.index(len)).collect();
+
+                let mut levels = vec![rng.range(1..100)];
+                for i in 1..len {
+                    let step = if bad.contains(&i) {
+                        *rng.choose(&[0, 4, 5, -direction, -2 * direction])
+                    } else {
+                        direction * rng.range(1..4)
+                    };
+                    levels.push(levels[i - 1] + step);
+                }
+
+                // Keep the levels positive, like the real input
+                let shift = 1 - levels.iter().min().copied().unwrap_or(1).min(1);
+                levels
+                    .iter()
+                    .map(|level| (level + shift).to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    + "\n"
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solution;
+
+    #[test]
+    fn test_generate_parses() {
+        let input = Day2::generate(&mut Rng::new(2), 100);
+        let reports = Day2::parse(&input).unwrap();
+        assert_eq!(reports.len(), 100);
+        assert!(reports.iter().flatten().all(|&level| level > 0));
+
+        // Some reports are safe and some aren't
+        let [safe, dampened] = Day2::solve_str(&input).unwrap();
+        assert_ne!(safe, 0.into());
+        assert_ne!(safe, 100.into());
+        assert_ne!(safe, dampened);
+    }
+}
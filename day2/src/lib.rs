@@ -0,0 +1,174 @@
+mod generate;
+
+use aoc_common::differential::Variant;
+use aoc_common::error;
+use aoc_common::{par_map, Answer, Part, Solution};
+
+fn is_valid_row_with_fail(row: &[i32], max_step: i32) -> bool {
+    if is_valid_row(row, max_step) {
+        return true;
+    } else {
+        for i in 0..row.len() {
+            if is_valid_row(&[&row[0..i], &row[i + 1..row.len()]].concat(), max_step) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+// The same as is_valid_row_with_fail, but only tries removing the levels that
+// could fix the first bad step: either side of it, or one of the first two
+// levels in case they set the wrong direction. Any other removal leaves that
+// step in place, so this checks four rows rather than one per level
+fn is_valid_row_with_fail_linear(row: &[i32], max_step: i32) -> bool {
+    if row.len() <= 1 {
+        return false;
+    }
+
+    let direction = step(row, 0);
+    let first_bad = (0..row.len() - 1).find(|&i| is_bad_step(step(row, i), direction, max_step));
+
+    match first_bad {
+        None => true,
+        Some(i) => [0, 1, i, i + 1]
+            .iter()
+            .any(|&skip| is_valid_row(&[&row[..skip], &row[skip + 1..]].concat(), max_step)),
+    }
+}
+
+fn is_valid_row(row: &[i32], max_step: i32) -> bool {
+    if row.len() <= 1 {
+        return false;
+    }
+
+    let direction = step(row, 0);
+    for i in 0..row.len() - 1 {
+        if is_bad_step(step(row, i), direction, max_step) {
+            return false;
+        }
+    }
+    true
+}
+
+// How far the level after i is from level i, widened so that levels at
+// either end of the i32 range don't overflow
+fn step(row: &[i32], i: usize) -> i64 {
+    i64::from(row[i + 1]) - i64::from(row[i])
+}
+
+// A step is bad if it stands still, goes the other way to the first step or
+// goes further than max_step
+fn is_bad_step(diff: i64, direction: i64, max_step: i32) -> bool {
+    diff.signum() * direction.signum() <= 0 || diff.abs() > i64::from(max_step)
+}
+
+// The most a level may change by from one to the next, unless the day2
+// binary is given another max step
+pub const MAX_STEP: i32 = 3;
+
+// Counts the reports whose levels all move the same way by at most max_step.
+// With the problem dampener, a report may have one bad level removed
+pub fn count_valid(reports: &[Vec<i32>], max_step: i32, dampener: bool) -> usize {
+    par_map(reports, |row| {
+        if dampener {
+            is_valid_row_with_fail(row, max_step)
+        } else {
+            is_valid_row(row, max_step)
+        }
+    })
+    .into_iter()
+    .filter(|&b| b)
+    .count()
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    // One report of levels per line
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> error::Result<Self::Input> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                line.split_whitespace()
+                    .map(|num_str| {
+                        error::parse_number::<i32>(num_str, i + 1, error::column(line, num_str))
+                    })
+                    .collect::<error::Result<Vec<i32>>>()
+            })
+            .collect()
+    }
+
+    // Counts the reports that are valid as they stand
+    fn part1(input: &Self::Input) -> error::Result<Answer> {
+        Ok(count_valid(input, MAX_STEP, false).into())
+    }
+
+    // Counts the reports that are valid once the problem dampener has removed at most one level
+    fn part2(input: &Self::Input) -> error::Result<Answer> {
+        Ok(count_valid(input, MAX_STEP, true).into())
+    }
+
+    fn variants() -> Vec<Variant<Self::Input>> {
+        vec![Variant {
+            part: Part::Two,
+            name: "is_valid_row_with_fail vs is_valid_row_with_fail_linear",
+            reference: Self::part2,
+            candidate: |input| {
+                Ok(input
+                    .iter()
+                    .filter(|row| is_valid_row_with_fail_linear(row, MAX_STEP))
+                    .count()
+                    .into())
+            },
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::differential;
+
+    #[test]
+    fn test_fail_linear_agrees_with_reference() {
+        differential::assert_agree::<Day2>(0, 500, 20);
+    }
+
+    #[test]
+    fn test_fail_linear_wrong_direction_at_start() {
+        // Only removing the first level fixes this one
+        assert!(is_valid_row_with_fail_linear(&[5, 1, 2, 3], MAX_STEP));
+        assert!(!is_valid_row_with_fail_linear(&[5, 1, 2, 3, 1], MAX_STEP));
+    }
+
+    #[test]
+    fn test_count_valid_with_max_step() {
+        let reports = Day2::parse(include_str!("../test.txt")).unwrap();
+        assert_eq!(count_valid(&reports, MAX_STEP, false), 2);
+        assert_eq!(count_valid(&reports, 4, false), 3);
+        assert_eq!(count_valid(&reports, 1, true), 0);
+    }
+
+    #[test]
+    fn test_extreme_levels() {
+        let (min, max) = (i32::MIN, i32::MAX);
+        assert!(!is_valid_row(&[min, max], MAX_STEP));
+        assert!(!is_valid_row(&[max, min, max], i32::MAX));
+        assert!(is_valid_row(&[max - 2, max - 1, max], MAX_STEP));
+        assert!(is_valid_row_with_fail(&[min, max, max - 1], MAX_STEP));
+        assert!(is_valid_row_with_fail_linear(
+            &[min, max, max - 1],
+            MAX_STEP
+        ));
+        assert!(!is_valid_row_with_fail_linear(
+            &[min, max, min, max],
+            MAX_STEP
+        ));
+    }
+}
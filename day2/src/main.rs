@@ -1,61 +1,35 @@
-use std::env;
-use std::fs::File;
-use std::io::{self, prelude::*, BufReader};
-
-fn is_valid_row_with_fail(row: &[i32]) -> bool {
-    if is_valid_row(row) {
-        return true;
-    }
-    else {
-        for i in 0..row.len() {
-            if is_valid_row(&[&row[0..i],&row[i+1..row.len()]].concat()) {
-                return true;
-            }
+use aoc_common::{input, output, Args, Part, Solution};
+use day2::{Day2, MAX_STEP};
+
+fn main() {
+    let args = Args::from_env::<Day2>(
+        &["max_step"],
+        &["<filename> [<max step>]    (levels may change by 1 to max step, 3 by default)"],
+    );
+    let max_step = match args.param("max_step").map(str::parse) {
+        Some(Ok(max_step)) => max_step,
+        Some(Err(_)) => {
+            eprintln!("error: max step must be a whole number");
+            std::process::exit(1);
         }
-    }
-    false
-}
-
-fn is_valid_row(row: &[i32]) -> bool {
-    if row.len() <= 1 {
-        return false;
-    }
-
-    let direction = row[1] - row[0];
-    for i in 0..row.len() - 1 {
-        let diff = row[i + 1] - row[i];
-
-        if diff * direction <= 0 || diff.abs() > 3{
-            return false;
-        }
-    }
-    true
-}
-
-fn main() -> io::Result<()> {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() != 2 {
-        eprintln!("Usage: {} <filename>", args[0]);
-        std::process::exit(1);
-    }
-
-    let filename = &args[1];
-    let file = File::open(filename)?;
-    let reader = BufReader::new(file);
-
-    let valid_count = reader
-        .lines()
-        .map(|line| line.unwrap())
-        .map(|line| {
-            line.split_whitespace()
-                .map(|num_str| num_str.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
-        })
-        .filter(|row| is_valid_row_with_fail(row))
-        .count();
-
-    println!("Number of valid rows: {}", valid_count);
-
-    Ok(())
+        None => MAX_STEP,
+    };
+    let input = input::parse_file::<Day2>(&args.filename).unwrap_or_else(|e| e.exit());
+
+    output::print_solved(
+        args.format,
+        "Number of valid rows",
+        Day2::DAY,
+        Part::One,
+        || Ok(day2::count_valid(&input, max_step, false).into()),
+    )
+    .unwrap_or_else(|e| e.exit());
+    output::print_solved(
+        args.format,
+        "Number of valid rows with dampener",
+        Day2::DAY,
+        Part::Two,
+        || Ok(day2::count_valid(&input, max_step, true).into()),
+    )
+    .unwrap_or_else(|e| e.exit());
 }
@@ -0,0 +1,31 @@
+// Runs day 2 against the example in test.txt, the way the puzzle describes it
+use aoc_common::{Answer, Solution};
+use day2::Day2;
+use std::process::Command;
+
+const EXAMPLE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/test.txt");
+
+fn run_main(args: &[&str]) -> String {
+    // An empty AOC_CONFIG ignores any .aoc.toml around the checkout
+    let output = Command::new(env!("CARGO_BIN_EXE_day2"))
+        .args(args)
+        .env("AOC_CONFIG", "")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_example_answers() {
+    let input = include_str!("../test.txt");
+    assert_eq!(Day2::solve_str(input), Ok([Answer::Int(2), Answer::Int(5)]));
+}
+
+#[test]
+fn test_main_prints_example_answers() {
+    assert_eq!(
+        run_main(&[EXAMPLE]),
+        "Number of valid rows: 2\nNumber of valid rows with dampener: 5\n"
+    );
+}
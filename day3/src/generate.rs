@@ -0,0 +1,70 @@
+use crate::Day3;
+use aoc_common::{Generate, Rng};
+
+// Things that look a bit like instructions but aren't
+const CORRUPTED: [&str; 10] = [
+    "mul(4*",
+    "mul ( 2 , 4 )",
+    "mul(6,9!",
+    "?(12,34)",
+    "mul[3,7]",
+    "mul(1234,5)",
+    "do_not_mul(5,5)",
+    "don't",
+    "do(",
+    "mul(,)",
+];
+
+// Stray characters from the corrupted memory
+const NOISE: &[u8] = b"!@#$%^&*()[]{}<>,;:?'+-_/ whatfromselectwhy";
+
+impl Generate for Day3 {
+    // `size` pieces of memory: real mul(x,y)s, do()s and don't()s mixed in
+    // with corrupted instructions and noise, split over a few lines
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut memory = String::new();
+        for _ in 0..size {
+            match rng.range(0..10) {
+                0..=2 => memory += &format!("mul({},{})", rng.range(1..1000), rng.range(1..1000)),
+                3 => memory += "do()",
+                4 => memory += "don't()",
+                5..=6 => memory += *rng.choose(&CORRUPTED),
+                _ => {
+                    for _ in 0..rng.range(1..6) {
+                        memory.push(*rng.choose(NOISE) as char);
+                    }
+                }
+            }
+            if rng.chance(0.02) {
+                memory.push('\n');
+            }
+        }
+        memory.push('\n');
+        memory
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{Answer, Solution};
+
+    #[test]
+    fn test_generate_is_reproducible() {
+        let input = Day3::generate(&mut Rng::new(3), 200);
+        assert_eq!(input, Day3::generate(&mut Rng::new(3), 200));
+        assert!(input.contains("mul(") && input.contains("don't()"));
+
+        // The conditionals only ever switch multiplications off
+        let [Answer::Int(all), Answer::Int(enabled)] = Day3::solve_str(&input).unwrap() else {
+            panic!("Expected numeric answers");
+        };
+        assert!(enabled < all);
+    }
+
+    #[test]
+    fn test_large_input() {
+        let input = Day3::generate(&mut Rng::new(15), 200_000);
+        assert!(Day3::solve_str(&input).is_ok());
+    }
+}
@@ -0,0 +1,225 @@
+mod generate;
+
+use aoc_common::error;
+use aoc_common::{Answer, Solution};
+use regex::Regex;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+// Which instructions execute obeys; the rest are treated as corrupted.
+// Written as a comma separated list, e.g. "mul,do,don't"
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Instructions {
+    pub mul: bool,
+    pub enable: bool,
+    pub disable: bool,
+}
+
+impl Instructions {
+    // Part one only multiplies
+    pub const MUL: Instructions = Instructions {
+        mul: true,
+        enable: false,
+        disable: false,
+    };
+
+    // Part two also follows do() and don't()
+    pub const ALL: Instructions = Instructions {
+        mul: true,
+        enable: true,
+        disable: true,
+    };
+}
+
+impl FromStr for Instructions {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut instructions = Instructions::default();
+        for name in value
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            match name {
+                "mul" => instructions.mul = true,
+                "do" => instructions.enable = true,
+                "don't" => instructions.disable = true,
+                _ => {
+                    return Err(format!(
+                        "Unknown instruction {:?}, expected mul, do or don't",
+                        name
+                    ))
+                }
+            }
+        }
+        Ok(instructions)
+    }
+}
+
+// One instruction found in the corrupted memory
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Mul(i64, i64),
+    Do,
+    Dont,
+}
+
+// The instructions in the corrupted memory, in order, with the corrupted
+// text around them dropped. Parsing checks that every multiplication added
+// together fits, and none are negative, so any of them added up fit too
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Memory {
+    commands: Vec<Command>,
+}
+
+// The operands and closing bracket that must follow "mul("
+static OPERANDS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^([0-9]+),([0-9]+)\)").unwrap());
+
+impl Memory {
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    // Sums the multiplications in the memory, obeying only the given
+    // instructions. Once don't() has turned them off, only do() turns them
+    // back on
+    pub fn sum(&self, instructions: Instructions) -> i128 {
+        let mut total = 0;
+        let mut enabled = true;
+        for command in &self.commands {
+            match *command {
+                Command::Mul(x, y) if instructions.mul && enabled => total += x as i128 * y as i128,
+                Command::Do if instructions.enable => enabled = true,
+                Command::Dont if instructions.disable => enabled = false,
+                _ => {}
+            }
+        }
+        total
+    }
+}
+
+impl FromStr for Memory {
+    type Err = error::Error;
+
+    // Steps through each line a character at a time, picking out mul(x,y),
+    // do() and don't(). Instructions never span lines
+    fn from_str(input: &str) -> error::Result<Self> {
+        let mut commands = Vec::new();
+        for (number, line) in input.lines().enumerate() {
+            let mut rest = line;
+            while !rest.is_empty() {
+                if let Some(after) = rest.strip_prefix("mul(") {
+                    rest = after;
+                    if let Some(captures) = OPERANDS.captures(after) {
+                        let operand = |i: usize| {
+                            let text = &captures[i];
+                            error::parse_number(text, number + 1, error::column(line, text))
+                        };
+                        commands.push(Command::Mul(operand(1)?, operand(2)?));
+                        rest = &after[captures[0].len()..];
+                    }
+                } else if let Some(after) = rest.strip_prefix("don't()") {
+                    commands.push(Command::Dont);
+                    rest = after;
+                } else if let Some(after) = rest.strip_prefix("do()") {
+                    commands.push(Command::Do);
+                    rest = after;
+                } else {
+                    let mut chars = rest.chars();
+                    chars.next();
+                    rest = chars.as_str();
+                }
+            }
+        }
+
+        commands
+            .iter()
+            .try_fold(0i128, |total, command| match *command {
+                Command::Mul(x, y) => total.checked_add(x as i128 * y as i128),
+                _ => Some(total),
+            })
+            .ok_or_else(|| error::Error::overflow("the sum of the multiplications", "i128"))?;
+        Ok(Memory { commands })
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    // The instructions in the corrupted memory
+    type Input = Memory;
+
+    fn parse(input: &str) -> error::Result<Self::Input> {
+        input.parse()
+    }
+
+    // Sums every multiplication in the memory
+    fn part1(input: &Self::Input) -> error::Result<Answer> {
+        Ok(input.sum(Instructions::MUL).into())
+    }
+
+    // Sums the multiplications that are enabled by the do() and don't() instructions
+    fn part2(input: &Self::Input) -> error::Result<Answer> {
+        Ok(input.sum(Instructions::ALL).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMORY: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn test_parse_instructions() {
+        assert_eq!("mul".parse(), Ok(Instructions::MUL));
+        assert_eq!(" mul, do ,don't".parse(), Ok(Instructions::ALL));
+        assert_eq!("".parse(), Ok(Instructions::default()));
+        assert!("mul,dont".parse::<Instructions>().is_err());
+    }
+
+    #[test]
+    fn test_sum_with_instructions() {
+        let memory: Memory = MEMORY.parse().unwrap();
+        assert_eq!(memory.sum(Instructions::MUL), 161);
+        assert_eq!(memory.sum(Instructions::ALL), 48);
+        // Nothing turns the multiplications after don't() back on
+        assert_eq!(memory.sum("mul,don't".parse().unwrap()), 8);
+        assert_eq!(memory.sum("do,don't".parse().unwrap()), 0);
+    }
+
+    // Long enough that going one character at a time recursively would
+    // overflow the stack
+    #[test]
+    fn test_long_input() {
+        let memory = format!("{}mul(2,3){}", "x".repeat(1_000_000), "y".repeat(1_000_000));
+        let memory: Memory = memory.parse().unwrap();
+        assert_eq!(memory.commands(), [Command::Mul(2, 3)]);
+        assert_eq!(memory.sum(Instructions::ALL), 6);
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        let memory: Memory = "ab\u{e9}mul(2,3)\u{e9}do()\u{1f600}mul(\u{e9}"
+            .parse()
+            .unwrap();
+        assert_eq!(memory.commands(), [Command::Mul(2, 3), Command::Do]);
+    }
+
+    #[test]
+    fn test_operand_out_of_range() {
+        assert_eq!(
+            "mul(2,3)\nxmul(99999999999999999999,2)".parse::<Memory>(),
+            Err(error::Error::InvalidNumber {
+                location: error::Location::new(2, 6),
+                text: "99999999999999999999".to_string()
+            })
+        );
+        let memory: Memory = "mul(99999999999,2)".parse().unwrap();
+        assert_eq!(memory.sum(Instructions::MUL), 199999999998);
+    }
+}
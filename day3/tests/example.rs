@@ -0,0 +1,34 @@
+// Runs day 3 against the example in test.txt, the way the puzzle describes it
+use aoc_common::{Answer, Solution};
+use day3::Day3;
+use std::process::Command;
+
+const EXAMPLE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/test.txt");
+
+fn run_main(args: &[&str]) -> String {
+    // An empty AOC_CONFIG ignores any .aoc.toml around the checkout
+    let output = Command::new(env!("CARGO_BIN_EXE_day3"))
+        .args(args)
+        .env("AOC_CONFIG", "")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_example_answers() {
+    let input = include_str!("../test.txt");
+    assert_eq!(
+        Day3::solve_str(input),
+        Ok([Answer::Int(161), Answer::Int(48)])
+    );
+}
+
+#[test]
+fn test_main_prints_example_answers() {
+    assert_eq!(
+        run_main(&[EXAMPLE]),
+        "sum: 161\nsum with conditionals: 48\n"
+    );
+}
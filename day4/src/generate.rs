@@ -0,0 +1,32 @@
+use crate::Day4;
+use aoc_common::{Generate, Rng};
+
+impl Generate for Day4 {
+    // A `size` by `size` grid of the letters in XMAS, so that plenty of
+    // XMASes and X-MASes turn up by chance
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let mut row: String = (0..size)
+                    .map(|_| *rng.choose(&['X', 'M', 'A', 'S']))
+                    .collect();
+                row.push('\n');
+                row
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solution;
+
+    #[test]
+    fn test_generate_parses() {
+        let input = Day4::generate(&mut Rng::new(4), 12);
+        assert_eq!(input.lines().count(), 12);
+        assert!(input.lines().all(|line| line.len() == 12));
+        assert!(Day4::parse(&input).is_ok());
+    }
+}
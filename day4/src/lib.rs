@@ -0,0 +1,31 @@
+mod board;
+mod generate;
+
+use aoc_common::error;
+use aoc_common::{Answer, Grid, Solution};
+pub use board::Board;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Board;
+
+    fn parse(input: &str) -> error::Result<Self::Input> {
+        // The grid rejects any row that isn't as wide as the first
+        let board: Grid<char> = input.parse()?;
+
+        Ok(Board::new(board))
+    }
+
+    // Counts every occurrence of XMAS in any direction
+    fn part1(board: &Self::Input) -> error::Result<Answer> {
+        Ok(board.solve("XMAS").into())
+    }
+
+    // Counts the MAS crosses
+    fn part2(board: &Self::Input) -> error::Result<Answer> {
+        Ok(board.solve_xmas().into())
+    }
+}
@@ -1,33 +1,29 @@
-use std::env;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-mod board;
+use aoc_common::{input, output, Args, Part, Solution};
+use day4::Day4;
 
-fn main() -> std::io::Result<()> {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() < 2 || args.len() > 3 {
-        eprintln!("Usage for word solution: {} <filename> <word>", args[0]);
-        eprintln!("e.g: {} input.txt XMAS", args[0]);
-        eprintln!("Usage for X-MAS solution: {} <filename>", args[0]);
-        eprintln!("e.g: {} input.txt", args[0]);
-        std::process::exit(1);
-    }
-
-    let file = File::open(&args[1])?;
-    let reader = BufReader::new(file);
-
-    let board: board::Board = board::Board::new(
-        reader
-            .lines()
-            .map(|line| line.unwrap().chars().collect::<Vec<char>>())
-            .collect(),
+fn main() {
+    let args = Args::from_env::<Day4>(
+        &["word"],
+        &[
+            "<filename> <word>    (word solution, e.g. input.txt XMAS)",
+            "<filename>           (XMAS and X-MAS solutions, e.g. input.txt)",
+        ],
     );
 
-    if args.len() == 3 {
-        println!("Word count: {}", board.solve(&args[2]));
-    } else {
-        println!("X-mas count: {}", board.solve_xmas());
+    let board = input::parse_file::<Day4>(&args.filename).unwrap_or_else(|e| e.exit());
+
+    match args.param("word") {
+        Some(word) => {
+            output::print_solved(args.format, "Word count", Day4::DAY, Part::One, || {
+                Ok(board.solve(word).into())
+            })
+            .unwrap_or_else(|e| e.exit());
+        }
+        None => {
+            output::print_part::<Day4>(args.format, &board, Part::One, "Word count")
+                .unwrap_or_else(|e| e.exit());
+            output::print_part::<Day4>(args.format, &board, Part::Two, "X-mas count")
+                .unwrap_or_else(|e| e.exit());
+        }
     }
-    Ok(())
 }
@@ -0,0 +1,92 @@
+// Runs day 4 against the example in test.txt, the way the puzzle describes it
+use aoc_common::{Answer, Solution};
+use day4::Day4;
+use std::process::Command;
+
+const EXAMPLE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/test.txt");
+
+fn run_main(args: &[&str]) -> String {
+    // An empty AOC_CONFIG ignores any .aoc.toml around the checkout
+    let output = Command::new(env!("CARGO_BIN_EXE_day4"))
+        .args(args)
+        .env("AOC_CONFIG", "")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_example_answers() {
+    let input = include_str!("../test.txt");
+    assert_eq!(
+        Day4::solve_str(input),
+        Ok([Answer::Int(18), Answer::Int(9)])
+    );
+}
+
+#[test]
+fn test_main_prints_example_answers() {
+    assert_eq!(run_main(&[EXAMPLE]), "Word count: 18\nX-mas count: 9\n");
+}
+
+#[test]
+fn test_main_counts_empty_and_non_ascii_words() {
+    assert_eq!(run_main(&[EXAMPLE, "", "-vv"]), "Word count: 0\n");
+    assert_eq!(run_main(&[EXAMPLE, "\u{c9}X", "-vv"]), "Word count: 0\n");
+
+    let board = Day4::parse("\u{c9}X\nX\u{c9}\n").unwrap();
+    assert_eq!(board.solve("X\u{c9}"), 4);
+}
+
+#[test]
+fn test_main_counts_given_word() {
+    assert_eq!(run_main(&[EXAMPLE, "XMAS"]), "Word count: 18\n");
+}
+
+#[test]
+fn test_main_logs_to_stderr() {
+    let output = Command::new(env!("CARGO_BIN_EXE_day4"))
+        .args([EXAMPLE, "-v"])
+        .env("AOC_CONFIG", "")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stdout, "Word count: 18\nX-mas count: 9\n");
+    assert_eq!(stderr.matches("Found word finishing at").count(), 18);
+}
+
+#[test]
+fn test_main_reads_config() {
+    let config = std::env::temp_dir().join(format!("aoc-day4-config-{}.toml", std::process::id()));
+    std::fs::write(
+        &config,
+        format!(
+            "format = \"json\"\n\n[day4]\ninput = {:?}\nword = \"MAS\"\n",
+            EXAMPLE
+        ),
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_day4"))
+            .args(args)
+            .env("AOC_CONFIG", &config)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    // The input, word and format all come from the config...
+    assert!(run(&[]).starts_with("{\"day\":4,\"part\":1,\"answer\":38,"));
+    // ...unless the command line says otherwise
+    assert_eq!(run(&["--format", "text"]), "Word count: 38\n");
+    assert_eq!(
+        run(&["--format", "text", EXAMPLE, "XMA"]),
+        "Word count: 27\n"
+    );
+
+    std::fs::remove_file(&config).unwrap();
+}
@@ -0,0 +1,62 @@
+use crate::Day5;
+use aoc_common::{Generate, Rng};
+
+impl Generate for Day5 {
+    // `size` two digit pages in a hidden order, a rule for every pair of them
+    // so the rules form a DAG that orders any update one way only, and `size`
+    // updates of an odd number of pages. About half the updates are already
+    // in order
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut pages: Vec<i64> = (10..10 + size.max(90) as i64).collect();
+        rng.shuffle(&mut pages);
+        pages.truncate(size);
+
+        let mut rules = Vec::new();
+        for (i, x) in pages.iter().enumerate() {
+            for y in &pages[i + 1..] {
+                rules.push(format!("{}|{}\n", x, y));
+            }
+        }
+        rng.shuffle(&mut rules);
+
+        let mut input: String = rules.concat();
+        input.push('\n');
+        let longest = if size % 2 == 1 {
+            size
+        } else {
+            size.saturating_sub(1)
+        };
+        for _ in 0..size {
+            let len = (2 * rng.range(0..12) as usize + 1).min(longest);
+            let mut positions: Vec<usize> = (0..size).collect();
+            rng.shuffle(&mut positions);
+            positions.truncate(len);
+            if rng.chance(0.5) {
+                positions.sort();
+            }
+            let update: Vec<String> = positions.iter().map(|&i| pages[i].to_string()).collect();
+            input += &update.join(",");
+            input.push('\n');
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solution;
+
+    #[test]
+    fn test_generate_parses() {
+        let input = Day5::generate(&mut Rng::new(5), 20);
+        let (rules, updates) = Day5::parse(&input).unwrap();
+        assert_eq!(updates.len(), 20);
+        assert!(updates.iter().all(|update| update.len() % 2 == 1));
+
+        // Every update can be put in order
+        for update in &updates {
+            assert!(rules.is_match(&rules.correct(update)));
+        }
+    }
+}
@@ -0,0 +1,141 @@
+pub mod rule;
+pub mod rules;
+
+mod generate;
+
+use aoc_common::differential::Variant;
+use aoc_common::error::{self, Error};
+use aoc_common::{Answer, Part, Solution};
+use rules::Rules;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    // The ordering rules and the list of updates
+    type Input = (Rules, Vec<Vec<u32>>);
+
+    fn parse(input: &str) -> error::Result<Self::Input> {
+        // Read all lines into a vector
+        let lines: Vec<String> = input.lines().map(String::from).collect();
+
+        // Find the index of the blank line
+        let blank_line_index = lines
+            .iter()
+            .position(|line| line.trim().is_empty())
+            .ok_or_else(|| {
+                Error::expected(
+                    lines.len() + 1,
+                    1,
+                    "a blank line between the rules and the updates",
+                )
+            })?;
+
+        // Split the vector into two slices
+        let rules = Rules::from_lines(&lines[..blank_line_index])?;
+        let pages_lines = &lines[blank_line_index + 1..];
+
+        let updates = pages_lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let line_number = blank_line_index + i + 2;
+                let pages = line
+                    .split(',')
+                    .map(|num| {
+                        let num = num.trim();
+                        error::parse_number::<u32>(num, line_number, error::column(line, num))
+                    })
+                    .collect::<error::Result<Vec<u32>>>()?;
+                // Rules that go round in a circle could never all be
+                // matched, so there would be no correcting the update
+                if !rules.can_order(&pages) {
+                    return Err(Error::expected(
+                        line_number,
+                        1,
+                        "pages that the rules don't order in a circle",
+                    ));
+                }
+                Ok(pages)
+            })
+            .collect::<error::Result<Vec<Vec<u32>>>>()?;
+
+        Ok((rules, updates))
+    }
+
+    // Sums the middle page of every update that already matches the rules
+    fn part1((rules, updates): &Self::Input) -> error::Result<Answer> {
+        let matched_updates = updates.iter().filter(|pages| rules.is_match(pages));
+
+        // Sum the middle page from each of the matched_updates
+        Ok(matched_updates
+            .map(|pages| pages[pages.len() / 2])
+            .sum::<u32>()
+            .into())
+    }
+
+    // Corrects every update that doesn't match the rules and sums their middle pages
+    fn part2((rules, updates): &Self::Input) -> error::Result<Answer> {
+        let unmatched_updates = updates.iter().filter(|pages| !rules.is_match(pages));
+
+        Ok(unmatched_updates
+            .map(|pages| rules.correct(pages))
+            .map(|pages| pages[pages.len() / 2])
+            .sum::<u32>()
+            .into())
+    }
+
+    fn variants() -> Vec<Variant<Self::Input>> {
+        vec![Variant {
+            part: Part::Two,
+            name: "Rules::correct vs Rules::correct_by_counting",
+            reference: Self::part2,
+            candidate: |(rules, updates)| {
+                Ok(updates
+                    .iter()
+                    .filter(|pages| !rules.is_match(pages))
+                    .map(|pages| rules.correct_by_counting(pages))
+                    .map(|pages| pages[pages.len() / 2])
+                    .sum::<u32>()
+                    .into())
+            },
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::differential;
+
+    #[test]
+    fn test_solve_str() {
+        let input = "1|2\n2|3\n\n1,2,3\n3,2,1\n";
+        assert_eq!(Day5::solve_str(input), Ok([Answer::Int(2), Answer::Int(2)]));
+    }
+
+    #[test]
+    fn test_correct_by_counting_agrees_with_correct() {
+        differential::assert_agree::<Day5>(0, 50, 15);
+    }
+
+    #[test]
+    fn test_cyclic_rules_are_an_error() {
+        assert_eq!(
+            Day5::solve_str("1|2\n2|1\n\n3,4\n2,1\n"),
+            Err(Error::expected(
+                5,
+                1,
+                "pages that the rules don't order in a circle"
+            ))
+        );
+        // Only the rules between an update's own pages matter
+        assert!(Day5::solve_str("1|2\n2|3\n3|1\n\n2,1\n").is_ok());
+    }
+
+    #[test]
+    fn test_solve_str_without_updates_section() {
+        assert!(Day5::solve_str("1|2\n2|3").is_err());
+    }
+}
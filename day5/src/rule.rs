@@ -1,19 +1,34 @@
+use aoc_common::error::{self, Error};
 use std::fmt;
+use std::str::FromStr;
 
 pub struct Rule {
     x: u32,
     y: u32,
 }
 
+impl FromStr for Rule {
+    type Err = Error;
+
+    // Parses an "x|y" rule. Errors are reported on line 1, for the caller to move
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (x, y) = line
+            .split_once('|')
+            .ok_or_else(|| Error::expected(1, line.len() + 1, "a rule like 47|53"))?;
+        Ok(Rule {
+            x: error::parse_number(x, 1, error::column(line, x))?,
+            y: error::parse_number(y, 1, error::column(line, y))?,
+        })
+    }
+}
+
 impl Rule {
-    pub fn from_str(line: &str) -> Self {
-        let parts: Vec<&str> = line.split('|').collect();
-        let x = parts[0].parse::<u32>().expect("Invalid number");
-        let y = parts[1].parse::<u32>().expect("Invalid number");
-        Rule { x, y }
+    // The page that has to come first and the page that has to come after it
+    pub fn pages(&self) -> (u32, u32) {
+        (self.x, self.y)
     }
 
-    pub fn is_match(&self, pages: &Vec<u32>) -> bool {
+    pub fn is_match(&self, pages: &[u32]) -> bool {
         // Get the first position of the first occurrence of x, or if it doesn't exist, return true
         match pages.iter().position(|&page| page == self.x) {
             Some(xpos) => match pages.iter().position(|&page| page == self.y) {
@@ -24,13 +39,13 @@ impl Rule {
         }
     }
 
-    pub fn correct(&self, pages: &Vec<u32>) -> Vec<u32> {
+    pub fn correct(&self, pages: &[u32]) -> Vec<u32> {
         // If we aren't correct then swap the x and y values
         match pages.iter().position(|&page| page == self.x) {
             Some(xpos) => match pages.iter().position(|&page| page == self.y) {
                 Some(ypos) => {
                     if xpos < ypos {
-                        pages.clone()
+                        pages.to_vec()
                     } else {
                         pages.iter().enumerate().map(|(i, &page)| {
                             if i == xpos {
@@ -43,9 +58,9 @@ impl Rule {
                         }).collect()
                     }
                 },
-                None => pages.clone(),
+                None => pages.to_vec(),
             },
-            None => pages.clone(),
+            None => pages.to_vec(),
         }
     }
 }
@@ -91,6 +106,31 @@ mod tests {
         assert!(rule.is_match(&pages));
     }
 
+    #[test]
+    fn test_rule_from_str() {
+        let rule: Rule = "47|53".parse().unwrap();
+        assert_eq!((rule.x, rule.y), (47, 53));
+    }
+
+    #[test]
+    fn test_rule_from_str_missing_separator() {
+        assert_eq!(
+            "4753".parse::<Rule>().err(),
+            Some(Error::expected(1, 5, "a rule like 47|53"))
+        );
+    }
+
+    #[test]
+    fn test_rule_from_str_invalid_number() {
+        assert_eq!(
+            "47|x3".parse::<Rule>().err(),
+            Some(Error::InvalidNumber {
+                location: error::Location::new(1, 4),
+                text: "x3".to_string()
+            })
+        );
+    }
+
     #[test]
     fn test_rule_correct_when_not_matching() {
         let rule = Rule { x: 1, y: 2 };
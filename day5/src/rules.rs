@@ -1,35 +1,83 @@
 use crate::rule::Rule;
+use aoc_common::error;
+use std::collections::HashSet;
 use std::fmt;
 
 pub struct Rules {
     rules: Vec<Rule>,
+    // Every (before, after) pair of pages, for looking rules up directly
+    pairs: HashSet<(u32, u32)>,
 }
 
 impl Rules {
-    pub fn from_lines(lines: &[String]) -> Self {
-        let rules = lines.iter().map(|line| Rule::from_str(line)).collect();
-        Rules { rules }
+    pub fn from_lines(lines: &[String]) -> error::Result<Self> {
+        let rules = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| line.parse::<Rule>().map_err(|e| e.on_line(i + 1)))
+            .collect::<error::Result<Vec<Rule>>>()?;
+        let pairs = rules.iter().map(Rule::pages).collect();
+        Ok(Rules { rules, pairs })
     }
 
-    pub fn is_match(&self, pages: &Vec<u32>) -> bool {
+    pub fn is_match(&self, pages: &[u32]) -> bool {
         self.rules.iter().all(|rule| rule.is_match(pages))
     }
 
-    pub fn correct(&self, pages: &Vec<u32>) -> Vec<u32> {
-        let corrected = self
-            .rules
-            .iter()
-            .fold(pages.clone(), |corrected_pages, rule| {
-                rule.correct(&corrected_pages)
-            });
+    // Whether the rules between these pages leave them an order to go in,
+    // rather than going round in a circle. Pages are taken away once none of
+    // those left have to come before them, until none are left or none can go
+    pub fn can_order(&self, pages: &[u32]) -> bool {
+        let mut remaining: HashSet<u32> = pages.iter().copied().collect();
+        loop {
+            let free: Vec<u32> = remaining
+                .iter()
+                .copied()
+                .filter(|&page| {
+                    !remaining
+                        .iter()
+                        .any(|&other| self.pairs.contains(&(other, page)))
+                })
+                .collect();
+            if free.is_empty() {
+                return remaining.is_empty();
+            }
+            for page in free {
+                remaining.remove(&page);
+            }
+        }
+    }
+
+    // The pages must be ones can_order accepts, as Day5::parse checks. Each
+    // swap then puts a pair the right way round for every order the rules
+    // allow, so going through the rules again and again does finish
+    pub fn correct(&self, pages: &[u32]) -> Vec<u32> {
+        let mut corrected = pages.to_vec();
 
         // Sometimes correcting on rule will knock another rule out of place
         // This can be corrected by running the correction again
-        if self.is_match(&corrected) {
-            corrected
-        } else {
-            self.correct(&corrected)
+        while !self.is_match(&corrected) {
+            corrected = self.rules.iter().fold(corrected, |corrected_pages, rule| {
+                rule.correct(&corrected_pages)
+            });
         }
+        corrected
+    }
+
+    // Puts the pages in order by counting, for each page, how many of the
+    // others the rules say must come before it. When the rules order every
+    // pair of pages in the update, as the puzzle promises, that count is the
+    // page's position, and this gives the same answer as correct without
+    // repeatedly going through every rule
+    pub fn correct_by_counting(&self, pages: &[u32]) -> Vec<u32> {
+        let mut corrected = pages.to_vec();
+        corrected.sort_by_key(|&page| {
+            pages
+                .iter()
+                .filter(|&&other| self.pairs.contains(&(other, page)))
+                .count()
+        });
+        corrected
     }
 }
 
@@ -49,7 +97,7 @@ mod tests {
     #[test]
     fn test_rules_is_match_all_rules_match() {
         let lines = vec!["1|2".to_string(), "3|4".to_string()];
-        let rules = Rules::from_lines(&lines);
+        let rules = Rules::from_lines(&lines).unwrap();
 
         let pages = vec![1, 2, 3, 4];
         assert!(rules.is_match(&pages));
@@ -58,7 +106,7 @@ mod tests {
     #[test]
     fn test_rules_is_match_some_rules_do_not_match() {
         let lines = vec!["1|2".to_string(), "3|4".to_string()];
-        let rules = Rules::from_lines(&lines);
+        let rules = Rules::from_lines(&lines).unwrap();
 
         let pages = vec![1, 2, 4, 3];
         assert!(!rules.is_match(&pages));
@@ -67,7 +115,7 @@ mod tests {
     #[test]
     fn test_rules_is_match_empty_pages() {
         let lines = vec!["1|2".to_string(), "3|4".to_string()];
-        let rules = Rules::from_lines(&lines);
+        let rules = Rules::from_lines(&lines).unwrap();
 
         let pages = vec![];
         assert!(rules.is_match(&pages));
@@ -76,16 +124,23 @@ mod tests {
     #[test]
     fn test_rules_is_match_empty_rules() {
         let lines = vec![];
-        let rules = Rules::from_lines(&lines);
+        let rules = Rules::from_lines(&lines).unwrap();
 
         let pages = vec![1, 2, 3, 4];
         assert!(rules.is_match(&pages));
     }
 
+    #[test]
+    fn test_rules_from_lines_reports_line() {
+        let lines = vec!["1|2".to_string(), "3-4".to_string()];
+        let error = Rules::from_lines(&lines).err().unwrap();
+        assert_eq!(error.to_string(), "<input>:2:4: expected a rule like 47|53");
+    }
+
     #[test]
     fn test_rule_correct_when_not_matching() {
         let lines = vec!["1|2".to_string()];
-        let rules = Rules::from_lines(&lines);
+        let rules = Rules::from_lines(&lines).unwrap();
 
         let pages = vec![2, 1];
         let corrected_pages = rules.correct(&pages);
@@ -95,7 +150,7 @@ mod tests {
     #[test]
     fn test_rule_correct_when_multiple_not_matching() {
         let lines = vec!["1|2".to_string(), "3|4".to_string()];
-        let rules = Rules::from_lines(&lines);
+        let rules = Rules::from_lines(&lines).unwrap();
 
         let pages = vec![2, 1, 4, 3];
         let corrected_pages = rules.correct(&pages);
@@ -105,10 +160,36 @@ mod tests {
     #[test]
     fn test_rule_correct_when_multiple_not_matching_require_recursion() {
         let lines = vec!["1|2".to_string(), "3|4".to_string(), "4|1".to_string()];
-        let rules = Rules::from_lines(&lines);
+        let rules = Rules::from_lines(&lines).unwrap();
 
         let pages = vec![2, 4, 1, 3];
         let corrected_pages = rules.correct(&pages);
         assert_eq!(corrected_pages, vec![3, 4, 1, 2]);
     }
+
+    #[test]
+    fn test_can_order() {
+        let lines = vec!["1|2".to_string(), "2|3".to_string(), "3|1".to_string()];
+        let rules = Rules::from_lines(&lines).unwrap();
+
+        assert!(rules.can_order(&[1, 2]));
+        assert!(rules.can_order(&[3, 4]));
+        assert!(!rules.can_order(&[2, 3, 1]));
+    }
+
+    #[test]
+    fn test_rule_correct_by_counting() {
+        let lines = vec![
+            "3|4".to_string(),
+            "3|1".to_string(),
+            "3|2".to_string(),
+            "4|1".to_string(),
+            "4|2".to_string(),
+            "1|2".to_string(),
+        ];
+        let rules = Rules::from_lines(&lines).unwrap();
+
+        let pages = vec![2, 4, 1, 3];
+        assert_eq!(rules.correct_by_counting(&pages), vec![3, 4, 1, 2]);
+    }
 }
@@ -0,0 +1,34 @@
+// Runs day 5 against the example in test.txt, the way the puzzle describes it
+use aoc_common::{Answer, Solution};
+use day5::Day5;
+use std::process::Command;
+
+const EXAMPLE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/test.txt");
+
+fn run_main(args: &[&str]) -> String {
+    // An empty AOC_CONFIG ignores any .aoc.toml around the checkout
+    let output = Command::new(env!("CARGO_BIN_EXE_day5"))
+        .args(args)
+        .env("AOC_CONFIG", "")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_example_answers() {
+    let input = include_str!("../test.txt");
+    assert_eq!(
+        Day5::solve_str(input),
+        Ok([Answer::Int(143), Answer::Int(123)])
+    );
+}
+
+#[test]
+fn test_main_prints_example_answers() {
+    assert_eq!(
+        run_main(&[EXAMPLE]),
+        "Sum of matched middle pages: 143\nSum of corrected middle pages: 123\n"
+    );
+}
@@ -0,0 +1,39 @@
+use crate::Day6;
+use aoc_common::{Generate, Rng};
+
+impl Generate for Day6 {
+    // A `size` by `size` map with obstructions on about one square in ten and
+    // a guard facing any direction on one of the clear squares
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1);
+        let mut map: Vec<Vec<char>> = (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if rng.chance(0.1) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        let (x, y) = (rng.index(size), rng.index(size));
+        map[y][x] = *rng.choose(&['^', '>', 'v', '<']);
+
+        map.iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solution;
+
+    #[test]
+    fn test_generate_parses() {
+        for seed in 0..20 {
+            let input = Day6::generate(&mut Rng::new(seed), 8);
+            assert!(Day6::parse(&input).is_ok(), "{}", input);
+        }
+        assert!(Day6::parse(&Day6::generate(&mut Rng::new(0), 0)).is_ok());
+    }
+}
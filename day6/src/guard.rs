@@ -1,47 +1,26 @@
 use crate::Map;
+use aoc_common::{par_map, Direction4, Point};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
+// Where the guard is and which way they are facing
+type State = (Point, Direction4);
+
 pub enum TravelResult {
     OutOfBounds,
     InfinitePath,
     GuardMoved(Guard),
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-pub enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
-impl Direction {
-    pub fn rotate90(self) -> Direction {
-        match self {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North,
-        }
-    }
-    pub fn apply(self, coord: (usize, usize)) -> (usize, usize) {
-        match self {
-            Direction::North => (coord.0, coord.1 - 1),
-            Direction::South => (coord.0, coord.1 + 1),
-            Direction::East => (coord.0 + 1, coord.1),
-            Direction::West => (coord.0 - 1, coord.1),
-        }
-    }
-}
-
+#[derive(Clone)]
 pub struct Guard {
-    position: (usize, usize),
-    direction: Direction,
-    path: Vec<((usize, usize), Direction)>,
+    position: Point,
+    direction: Direction4,
+    path: Vec<(Point, Direction4)>,
 }
 
 impl Guard {
-    pub fn new(position: (usize, usize), direction: Direction) -> Guard {
+    pub fn new(position: Point, direction: Direction4) -> Guard {
         Guard {
             position,
             direction,
@@ -59,24 +38,19 @@ impl Guard {
 
     pub fn travel(&self, map: &Map) -> TravelResult {
         // Test in bounds
-        if self.position.0 == 0 && self.direction == Direction::West
-            || self.position.1 == 0 && self.direction == Direction::North
-            || self.position.0 == map.width - 1 && self.direction == Direction::East
-            || self.position.1 == map.height - 1 && self.direction == Direction::South
-        {
-            return TravelResult::OutOfBounds;
-        }
-
-        let new_position = self.direction.apply(self.position);
+        let new_position = match self.position.checked_add(self.direction.vector()) {
+            Some(new_position) if map.board.contains(new_position) => new_position,
+            _ => return TravelResult::OutOfBounds,
+        };
 
         // Test for collision - if we collide rotate 90 degrees clockwise otherwise move
-        let new_guard = match map.board[new_position.1].chars().nth(new_position.0) {
-            Some('#') => Guard {
+        let new_guard = match map.board.get(new_position) {
+            Some(&'#') => Guard {
                 position: self.position,
-                direction: self.direction.rotate90(),
+                direction: self.direction.rotate_cw(),
                 path: [
                     self.path.clone(),
-                    vec![(self.position, self.direction.rotate90())],
+                    vec![(self.position, self.direction.rotate_cw())],
                 ]
                 .concat(),
             },
@@ -100,23 +74,91 @@ impl Guard {
     }
 
     pub fn block_infront_of(&self, map: &Map) -> Map {
-        let new_position = self.direction.apply(self.position);
-        let new_board: Vec<String> = map.board.iter().enumerate().map(|(y, row)| {
-            if y == new_position.1 {
-                let (start, end) = row.split_at(new_position.0);
-                let end = &end[1..]; // Skip the character at new_position.0
-                format!("{}#{}", start, end)
-            } else {
-                row.clone()
+        let mut new_board = map.board.clone();
+        if let Some(cell) = self
+            .position
+            .checked_add(self.direction.vector())
+            .and_then(|new_position| new_board.get_mut(new_position))
+        {
+            *cell = '#';
+        }
+
+        Map { board: new_board }
+    }
+
+    // Every position and direction the guard passes through, starting where
+    // they are now, until they leave the map or would start going round a
+    // loop. The fast counterpart to travel: it keeps the states in a set
+    // rather than searching and copying the path at every step
+    pub fn walk(&self, map: &Map) -> Vec<State> {
+        let mut seen: HashSet<State> = self.path.iter().copied().collect();
+        let mut walk = vec![(self.position, self.direction)];
+        while let Some(next) = step(walk[walk.len() - 1], map, None) {
+            if !seen.insert(next) {
+                break;
             }
-        }).collect();
-    
-        Map {
-            board: new_board,
-            width: map.width,
-            height: map.height,
+            walk.push(next);
+        }
+        walk
+    }
+
+    // The guard as travel leaves them after `steps` steps of their walk,
+    // path and all
+    pub fn after(&self, walk: &[State], steps: usize) -> Guard {
+        let (position, direction) = walk[steps];
+        Guard {
+            position,
+            direction,
+            path: [&self.path[..], &walk[1..=steps]].concat(),
         }
     }
+
+    // Counts the steps of the guard's walk where an obstruction in front of
+    // them would send them round a loop, as blocking in front of every guard
+    // on the path with block_infront_of does. Revisiting any state from
+    // earlier in the walk counts as a loop, just as it does for travel. Each
+    // step is checked on its own, so they are spread across threads
+    pub fn count_looping_obstructions(&self, map: &Map) -> usize {
+        let walk = self.walk(map);
+        let mut first_seen: HashMap<State, usize> =
+            self.path.iter().map(|&state| (state, 0)).collect();
+        for (i, &state) in walk.iter().enumerate() {
+            first_seen.entry(state).or_insert(i);
+        }
+
+        let steps: Vec<usize> = (0..walk.len()).collect();
+        par_map(&steps, |&i| {
+            let (position, direction) = walk[i];
+            let obstruction = position
+                .checked_add(direction.vector())
+                .filter(|&point| map.board.contains(point));
+
+            let mut state = (position, direction);
+            let mut seen = HashSet::new();
+            while let Some(next) = step(state, map, obstruction) {
+                if first_seen.get(&next).is_some_and(|&j| j <= i) || !seen.insert(next) {
+                    return true;
+                }
+                state = next;
+            }
+            false
+        })
+        .into_iter()
+        .filter(|&looped| looped)
+        .count()
+    }
+}
+
+// Where the guard goes next, turning right if something is in the way, or
+// None if they step off the map
+fn step((position, direction): State, map: &Map, obstruction: Option<Point>) -> Option<State> {
+    let next = position.checked_add(direction.vector())?;
+    match map.board.get(next) {
+        None => None,
+        Some('#') => Some((position, direction.rotate_cw())),
+        Some(_) if obstruction == Some(next) => Some((position, direction.rotate_cw())),
+        Some(_) => Some((next, direction)),
+    }
 }
 
 impl fmt::Display for Guard {
@@ -124,7 +166,7 @@ impl fmt::Display for Guard {
         write!(
             f,
             "Guard at ({}, {}), facing {:?}",
-            self.position.0, self.position.1, self.direction
+            self.position.x, self.position.y, self.direction
         )
     }
 }
@@ -135,14 +177,14 @@ mod tests {
 
     #[test]
     fn test_travel_within_bounds() {
-        match Map::from_lines(vec![String::from("."), String::from("^")]) {
+        match Map::new(".\n^".parse().unwrap()) {
             (Some(guard), map) => {
                 let new_guard = match guard.travel(&map) {
                     TravelResult::GuardMoved(new_guard) => new_guard,
                     _ => panic!("Expected TravelResult::GuardMoved"),
                 };
 
-                assert_eq!(new_guard.position, (0, 0));
+                assert_eq!(new_guard.position, Point::new(0, 0));
             }
             _ => panic!("Expected a guard"),
         }
@@ -150,7 +192,7 @@ mod tests {
 
     #[test]
     fn test_travel_out_of_bounds() {
-        match Map::from_lines(vec![String::from("^")]) {
+        match Map::new("^".parse().unwrap()) {
             (Some(guard), map) => {
                 assert!(
                     matches!(guard.travel(&map), TravelResult::OutOfBounds),
@@ -163,11 +205,11 @@ mod tests {
 
     #[test]
     fn test_guard_moves() {
-        match Map::from_lines(vec![String::from(".."), String::from(".<")]) {
+        match Map::new("..\n.<".parse().unwrap()) {
             (Some(guard), map) => match guard.travel(&map) {
                 TravelResult::GuardMoved(guard_moved) => {
-                    assert_eq!(guard_moved.position, (0, 1));
-                    assert_eq!(guard_moved.direction, Direction::West);
+                    assert_eq!(guard_moved.position, Point::new(0, 1));
+                    assert_eq!(guard_moved.direction, Direction4::West);
                 }
                 _ => panic!("Expected TravelResult::GuardMoved"),
             },
@@ -177,7 +219,7 @@ mod tests {
 
     #[test]
     fn test_guard_moves_count_is_unique() {
-        match Map::from_lines(vec![String::from("###"), String::from("#.<")]) {
+        match Map::new("###\n#.<".parse().unwrap()) {
             (Some(guard), map) => {
                 let guard_moved1 = match guard.travel(&map) {
                     TravelResult::GuardMoved(new_guard) => new_guard,
@@ -197,26 +239,22 @@ mod tests {
 
     #[test]
     fn test_guard_collision() {
-        match Map::from_lines(vec![String::from("#."), String::from("^.")]) {
+        match Map::new("#.\n^.".parse().unwrap()) {
             (Some(guard), map) => {
                 let new_guard = match guard.travel(&map) {
                     TravelResult::GuardMoved(new_guard) => new_guard,
                     _ => panic!("Expected TravelResult::GuardMoved"),
                 };
 
-                assert_eq!(new_guard.position, (0, 1));
-                assert_eq!(new_guard.direction, Direction::East);
+                assert_eq!(new_guard.position, Point::new(0, 1));
+                assert_eq!(new_guard.direction, Direction4::East);
             }
             _ => panic!("Expected a guard"),
         }
     }
     #[test]
     fn test_infinite_path() {
-        match Map::from_lines(vec![
-            String::from("###"),
-            String::from("#<#"),
-            String::from("###"),
-        ]) {
+        match Map::new("###\n#<#\n###".parse().unwrap()) {
             (Some(guard), map) => {
                 let result = std::iter::successors(Some(guard), |g| match g.travel(&map) {
                     TravelResult::GuardMoved(new_guard) => Some(new_guard),
@@ -235,14 +273,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_after_matches_travel() {
+        let map = Map {
+            board: "..#.\n...#\n....\n....\n".parse().unwrap(),
+        };
+        let guard = Guard::new(Point::new(1, 3), Direction4::North);
+        let walk = guard.walk(&map);
+
+        let mut travelled = guard.clone();
+        for steps in 1..walk.len() {
+            match travelled.travel(&map) {
+                TravelResult::GuardMoved(new_guard) => travelled = new_guard,
+                _ => panic!("guard stopped after {} steps", steps),
+            }
+            let after = guard.after(&walk, steps);
+            assert_eq!(after.position, travelled.position);
+            assert_eq!(after.direction, travelled.direction);
+            assert_eq!(after.path, travelled.path);
+        }
+    }
+
     #[test]
     fn test_block_infront_of() {
-        match Map::from_lines(vec![
-            String::from(".<"),
-        ]) {
+        match Map::new(".<".parse().unwrap()) {
+            (Some(guard), map) => {
+                let new_map = guard.block_infront_of(&map);
+                assert_eq!(new_map.to_string(), "#<\n");
+            }
+            _ => panic!("Expected a guard"),
+        }
+    }
+
+    #[test]
+    fn test_block_infront_of_edge() {
+        match Map::new("<.".parse().unwrap()) {
             (Some(guard), map) => {
                 let new_map = guard.block_infront_of(&map);
-                assert_eq!(new_map.board, vec![String::from("#<")]);
+                assert_eq!(new_map.to_string(), "<.\n");
             }
             _ => panic!("Expected a guard"),
         }
@@ -0,0 +1,137 @@
+mod generate;
+mod guard;
+mod map;
+
+pub use guard::{Guard, TravelResult};
+pub use map::Map;
+
+use aoc_common::differential::Variant;
+use aoc_common::error::{self, Error};
+use aoc_common::{debug, par_map, Answer, Grid, Part, Point, Solution};
+use std::collections::HashSet;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    // The guard at their starting position and the map they patrol
+    type Input = (Guard, Map);
+
+    fn parse(input: &str) -> error::Result<Self::Input> {
+        // The grid rejects any row that isn't as wide as the first, so the
+        // guard can move around it freely
+        let board: Grid<char> = input.parse()?;
+        if board.height() == 0 {
+            return Err(Error::EmptyInput { file: None });
+        }
+
+        match Map::new(board) {
+            (Some(guard), map) => Ok((guard, map)),
+            (None, map) => Err(Error::expected(
+                map.board.height(),
+                map.board.width() + 1,
+                "a guard (^, >, v or <) somewhere on the map",
+            )),
+        }
+    }
+
+    // Counts the unique locations the guard visits before leaving the map
+    fn part1((guard, map): &Self::Input) -> error::Result<Answer> {
+        Ok(travel_until_done(guard, map).into())
+    }
+
+    // Counts the steps on the guard's path where putting an obstruction in front
+    // of them would send them on an infinite path
+    fn part2((guard, map): &Self::Input) -> error::Result<Answer> {
+        Ok(guard.count_looping_obstructions(map).into())
+    }
+
+    fn variants() -> Vec<Variant<Self::Input>> {
+        vec![
+            Variant {
+                part: Part::One,
+                name: "Guard::travel vs Guard::walk",
+                reference: Self::part1,
+                candidate: |(guard, map)| {
+                    let positions: HashSet<Point> = guard
+                        .walk(map)
+                        .iter()
+                        .map(|&(position, _)| position)
+                        .collect();
+                    Ok(positions.len().into())
+                },
+            },
+            Variant {
+                part: Part::Two,
+                name: "Guard::block_infront_of vs Guard::count_looping_obstructions",
+                reference: |(guard, map)| Ok(count_by_blocking(guard, map).into()),
+                candidate: Self::part2,
+            },
+        ]
+    }
+}
+
+// The slow way to answer part two, kept to check the fast one against:
+// blocks in front of every guard on the path and sends them on their way
+fn count_by_blocking(guard: &Guard, map: &Map) -> usize {
+    // Use the guard to find out how many of them would
+    // head to an infinite path it they where rotated by 90%. Each step is
+    // checked on its own, so they are spread across threads, and logged
+    // afterwards so the lines come out in order
+    let walk = guard.walk(map);
+    let steps: Vec<usize> = (0..walk.len()).collect();
+    let looping = par_map(&steps, |&i| {
+        let g = guard.after(&walk, i);
+        is_on_infinite_path(&g, &g.block_infront_of(map))
+    });
+    for (i, &looped) in looping.iter().enumerate() {
+        debug!("Checked guard {}, looped: {}", i, looped);
+    }
+    looping.into_iter().filter(|&b| b).count()
+}
+
+fn travel_until_done(guard: &Guard, map: &Map) -> usize {
+    match guard.travel(map) {
+        TravelResult::GuardMoved(new_guard) => travel_until_done(&new_guard, map),
+        _ => guard.unique_path_count(),
+    }
+}
+
+// A loop rather than recursion, as the worker threads par_map starts have
+// smaller stacks than the main thread
+fn is_on_infinite_path(guard: &Guard, map: &Map) -> bool {
+    let mut guard = guard.clone();
+    loop {
+        match guard.travel(map) {
+            TravelResult::GuardMoved(new_guard) => guard = new_guard,
+            TravelResult::InfinitePath => return true,
+            TravelResult::OutOfBounds => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{differential, parallel};
+    use std::num::NonZeroUsize;
+
+    // The answer doesn't depend on how the steps are shared between threads.
+    // The thread count is shared by every test, so it's put back afterwards
+    #[test]
+    fn test_count_by_blocking_on_any_number_of_threads() {
+        let (guard, map) = Day6::parse(include_str!("../test.txt")).unwrap();
+        let before = NonZeroUsize::new(parallel::threads()).unwrap();
+        for threads in 1..=4 {
+            parallel::set_threads(NonZeroUsize::new(threads).unwrap());
+            assert_eq!(count_by_blocking(&guard, &map), 6);
+        }
+        parallel::set_threads(before);
+    }
+
+    #[test]
+    fn test_walk_agrees_with_travel() {
+        differential::assert_agree::<Day6>(0, 100, 8);
+    }
+}
@@ -1,47 +1,32 @@
-use crate::guard::{Direction, Guard};
+use crate::guard::Guard;
+use aoc_common::{Direction4, Grid};
 use std::fmt;
 
 pub struct Map {
-    // Define the fields for your map here
-    // For example:
-    pub board: Vec<String>,
-    pub width: usize,
-    pub height: usize
+    pub board: Grid<char>,
 }
 
 impl Map {
-    pub fn from_lines(board: Vec<String>) -> (Option<Guard>, Map) {
-        let width = board[0].len();
-        let height = board.len();
-        for (y, line) in board.iter().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-
-                let direction = match c {
-                    '^' => Some(Direction::North),
-                    '>' => Some(Direction::East),
-                    'v' => Some(Direction::South),
-                    '<' => Some(Direction::West),
-                    _ => None,
-                };
-
-                if let Some(direction) = direction {
-                    return (
-                        Some(Guard::new((x, y), direction)),
-                        Map { board, width, height},
-                    );
-                }
-            }
-        }
-        (None, Map { board, width, height})
+    // Splits the guard out of the map, if there is one. The guard's square
+    // stays on the board, it's just somewhere they can walk
+    pub fn new(board: Grid<char>) -> (Option<Guard>, Map) {
+        let guard = board.points().find_map(|point| {
+            let direction = match board[point] {
+                '^' => Some(Direction4::North),
+                '>' => Some(Direction4::East),
+                'v' => Some(Direction4::South),
+                '<' => Some(Direction4::West),
+                _ => None,
+            };
+            direction.map(|direction| Guard::new(point, direction))
+        });
+        (guard, Map { board })
     }
 }
 
 impl fmt::Display for Map {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for line in &self.board {
-            writeln!(f, "{}", line)?;
-        }
-        Ok(())
+        write!(f, "{}", self.board)
     }
 }
 
@@ -52,9 +37,9 @@ mod tests {
 
     #[test]
     fn test_guard_display() {
-        let board = vec![String::from("^."), String::from("..")];
+        let board = "^.\n..".parse().unwrap();
 
-        let (guard, _) = Map::from_lines(board);
+        let (guard, _) = Map::new(board);
 
         assert_eq!(
             format!("{}", guard.unwrap()),
@@ -64,9 +49,9 @@ mod tests {
 
     #[test]
     fn test_guard_at_1_0() {
-        let board = vec![String::from(".^"), String::from("..")];
+        let board = ".^\n..".parse().unwrap();
 
-        let (guard, _) = Map::from_lines(board);
+        let (guard, _) = Map::new(board);
 
         assert_eq!(
             format!("{}", guard.unwrap()),
@@ -76,9 +61,9 @@ mod tests {
 
     #[test]
     fn test_guard_at_1_0_east() {
-        let board = vec![String::from(".>"), String::from("..")];
+        let board = ".>\n..".parse().unwrap();
 
-        let (guard, _) = Map::from_lines(board);
+        let (guard, _) = Map::new(board);
 
         assert_eq!(
             format!("{}", guard.unwrap()),
@@ -88,9 +73,9 @@ mod tests {
 
     #[test]
     fn test_guard_at_1_0_south() {
-        let board = vec![String::from(".v"), String::from("..")];
+        let board = ".v\n..".parse().unwrap();
 
-        let (guard, _) = Map::from_lines(board);
+        let (guard, _) = Map::new(board);
 
         assert_eq!(
             format!("{}", guard.unwrap()),
@@ -100,9 +85,9 @@ mod tests {
 
     #[test]
     fn test_guard_at_1_0_west() {
-        let board = vec![String::from(".<"), String::from("..")];
+        let board = ".<\n..".parse().unwrap();
 
-        let (guard, _) = Map::from_lines(board);
+        let (guard, _) = Map::new(board);
 
         assert_eq!(
             format!("{}", guard.unwrap()),
@@ -110,4 +95,12 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_empty_board() {
+        let (guard, map) = Map::new("".parse().unwrap());
+
+        assert!(guard.is_none());
+        assert_eq!((map.board.width(), map.board.height()), (0, 0));
+    }
+
 }
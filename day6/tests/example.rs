@@ -0,0 +1,31 @@
+// Runs day 6 against the example in test.txt, the way the puzzle describes it
+use aoc_common::{Answer, Solution};
+use day6::Day6;
+use std::process::Command;
+
+const EXAMPLE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/test.txt");
+
+fn run_main(args: &[&str]) -> String {
+    // An empty AOC_CONFIG ignores any .aoc.toml around the checkout
+    let output = Command::new(env!("CARGO_BIN_EXE_day6"))
+        .args(args)
+        .env("AOC_CONFIG", "")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_example_answers() {
+    let input = include_str!("../test.txt");
+    assert_eq!(
+        Day6::solve_str(input),
+        Ok([Answer::Int(41), Answer::Int(6)])
+    );
+}
+
+#[test]
+fn test_main_prints_example_answers() {
+    assert_eq!(run_main(&[EXAMPLE]), "Unique locations visited: 41\nLocations leading to an infinite path if rotated by 90%: 6\n");
+}